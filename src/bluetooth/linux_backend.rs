@@ -0,0 +1,230 @@
+//! Linux環境向けのBluetoothバックエンド（BlueZ / D-Bus, bluer経由）
+
+use anyhow::{Context, Result};
+use tokio::runtime::Runtime;
+
+use super::{classify_device_type, BluetoothBackend, BluetoothDevice, BondState, ConnectionInfo};
+
+/// Linux環境向けのBluetoothバックエンド
+/// bluerは非同期APIのため、内部に小さなtokioランタイムを持ち、
+/// 同期的な `run_command` のフローを変えずに利用できるようにする
+pub struct LinuxBackend {
+    runtime: Runtime,
+}
+
+impl LinuxBackend {
+    /// 新しいLinuxBackendインスタンスを作成
+    pub fn new() -> Self {
+        let runtime = Runtime::new().expect("tokioランタイムの作成に失敗しました");
+        Self { runtime }
+    }
+
+    /// BlueZのデフォルトアダプタを取得する
+    async fn default_adapter() -> Result<bluer::Adapter> {
+        let session = bluer::Session::new().await
+            .context("BlueZセッションの開始に失敗しました")?;
+        session.default_adapter().await
+            .context("デフォルトのBluetoothアダプタの取得に失敗しました")
+    }
+
+    /// アダプタからDeviceInfo相当の一覧を作成する
+    async fn enumerate_devices(adapter: &bluer::Adapter) -> Result<Vec<BluetoothDevice>> {
+        let mut devices = Vec::new();
+
+        let addresses = adapter.device_addresses().await
+            .context("デバイスアドレス一覧の取得に失敗しました")?;
+
+        for address in addresses {
+            let device = adapter.device(address)
+                .context("デバイスハンドルの取得に失敗しました")?;
+
+            devices.push(Self::build_bluetooth_device(
+                address.to_string(),
+                device.name().await.unwrap_or(None),
+                device.is_connected().await.unwrap_or(false),
+                device.is_paired().await.unwrap_or(false),
+                device.class().await.unwrap_or(None),
+            ));
+        }
+
+        Ok(devices)
+    }
+
+    /// bluerから取得した生の値から`BluetoothDevice`を作成する
+    /// 名前未取得時はアドレスをフォールバックとして使う、といった純粋なマッピングのみを担い、
+    /// D-Bus呼び出しから切り離してテストできるようにする
+    fn build_bluetooth_device(
+        address: String,
+        name: Option<String>,
+        is_connected: bool,
+        is_paired: bool,
+        class_of_device: Option<u32>,
+    ) -> BluetoothDevice {
+        let class_of_device = class_of_device.unwrap_or(0);
+
+        BluetoothDevice {
+            name: name.unwrap_or_else(|| address.clone()),
+            address,
+            is_connected,
+            device_type: classify_device_type(class_of_device),
+            service_uuids: Vec::new(),
+            class_of_device,
+            bond_state: if is_paired { BondState::Bonded } else { BondState::NotBonded },
+        }
+    }
+}
+
+impl Default for LinuxBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BluetoothBackend for LinuxBackend {
+    fn list_devices(&mut self) -> Result<Vec<BluetoothDevice>> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            Self::enumerate_devices(&adapter).await
+        })
+    }
+
+    fn connect_device(&mut self, address: &str) -> Result<()> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            let addr: bluer::Address = address.parse()
+                .with_context(|| format!("無効なBluetoothアドレスです: {}", address))?;
+            let device = adapter.device(addr)
+                .context("デバイスハンドルの取得に失敗しました")?;
+            device.connect().await
+                .with_context(|| format!("デバイス {} への接続に失敗しました", address))
+        })
+    }
+
+    fn disconnect_device(&mut self, address: &str) -> Result<()> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            let addr: bluer::Address = address.parse()
+                .with_context(|| format!("無効なBluetoothアドレスです: {}", address))?;
+            let device = adapter.device(addr)
+                .context("デバイスハンドルの取得に失敗しました")?;
+            device.disconnect().await
+                .with_context(|| format!("デバイス {} からの切断に失敗しました", address))
+        })
+    }
+
+    fn is_connected(&self, address: &str) -> Result<bool> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            let addr: bluer::Address = address.parse()
+                .with_context(|| format!("無効なBluetoothアドレスです: {}", address))?;
+            let device = adapter.device(addr)
+                .context("デバイスハンドルの取得に失敗しました")?;
+            Ok(device.is_connected().await.unwrap_or(false))
+        })
+    }
+
+    fn connection_info(&self, address: &str) -> Result<ConnectionInfo> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            let addr: bluer::Address = address.parse()
+                .with_context(|| format!("無効なBluetoothアドレスです: {}", address))?;
+            let device = adapter.device(addr)
+                .context("デバイスハンドルの取得に失敗しました")?;
+
+            // BlueZはRSSI/TxPowerは公開するが、到達可能な最大送信電力は公開しないためNoneのまま
+            let rssi = device.rssi().await.unwrap_or(None);
+            let transmit_power = device.tx_power().await.unwrap_or(None);
+
+            Ok(ConnectionInfo {
+                rssi,
+                transmit_power,
+                max_transmit_power: None,
+            })
+        })
+    }
+
+    fn pair(&mut self, address: &str) -> Result<()> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            let addr: bluer::Address = address.parse()
+                .with_context(|| format!("無効なBluetoothアドレスです: {}", address))?;
+            let device = adapter.device(addr)
+                .context("デバイスハンドルの取得に失敗しました")?;
+            device.pair().await
+                .with_context(|| format!("デバイス {} とのペアリングに失敗しました", address))
+        })
+    }
+
+    fn remove_bond(&mut self, address: &str) -> Result<()> {
+        self.runtime.block_on(async {
+            let adapter = Self::default_adapter().await?;
+            let addr: bluer::Address = address.parse()
+                .with_context(|| format!("無効なBluetoothアドレスです: {}", address))?;
+            adapter.remove_device(addr).await
+                .with_context(|| format!("デバイス {} のボンディング解除に失敗しました", address))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_bluetooth_device_falls_back_to_address_when_name_missing() {
+        let device = LinuxBackend::build_bluetooth_device(
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            None,
+            false,
+            false,
+            None,
+        );
+
+        assert_eq!(device.name, "AA:BB:CC:DD:EE:FF");
+    }
+
+    #[test]
+    fn test_build_bluetooth_device_uses_name_when_present() {
+        let device = LinuxBackend::build_bluetooth_device(
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            Some("Test Headset".to_string()),
+            true,
+            true,
+            Some(0x240404),
+        );
+
+        assert_eq!(device.name, "Test Headset");
+        assert!(device.is_connected);
+    }
+
+    #[test]
+    fn test_build_bluetooth_device_maps_is_paired_to_bond_state() {
+        let bonded = LinuxBackend::build_bluetooth_device(
+            "AA:BB:CC:DD:EE:FF".to_string(), None, false, true, None,
+        );
+        assert_eq!(bonded.bond_state, BondState::Bonded);
+
+        let not_bonded = LinuxBackend::build_bluetooth_device(
+            "AA:BB:CC:DD:EE:FF".to_string(), None, false, false, None,
+        );
+        assert_eq!(not_bonded.bond_state, BondState::NotBonded);
+    }
+
+    #[test]
+    fn test_build_bluetooth_device_classifies_class_of_device() {
+        let device = LinuxBackend::build_bluetooth_device(
+            "AA:BB:CC:DD:EE:FF".to_string(), None, false, false, Some(0x240404),
+        );
+        assert_eq!(device.device_type, "Headset");
+        assert_eq!(device.class_of_device, 0x240404);
+    }
+
+    #[test]
+    fn test_build_bluetooth_device_missing_class_of_device_is_unknown() {
+        let device = LinuxBackend::build_bluetooth_device(
+            "AA:BB:CC:DD:EE:FF".to_string(), None, false, false, None,
+        );
+        assert_eq!(device.device_type, "Unknown");
+        assert_eq!(device.class_of_device, 0);
+    }
+}
@@ -1,14 +1,26 @@
 //! Bluetooth操作機能モジュール
-//! Windows Bluetooth APIを使用してBluetoothデバイスの管理を行う
+//! プラットフォームごとのバックエンドを介してBluetoothデバイスの管理を行う
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use windows::{
-    core::*,
-    Win32::Devices::Bluetooth::*,
-    Win32::Foundation::*,
-    Win32::System::Com::*,
-};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "windows")]
+mod windows_backend;
+#[cfg(target_os = "windows")]
+pub use windows_backend::WindowsBackend;
+
+#[cfg(target_os = "linux")]
+mod linux_backend;
+#[cfg(target_os = "linux")]
+pub use linux_backend::LinuxBackend;
 
 /// Bluetoothデバイス情報を表す構造体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +33,15 @@ pub struct BluetoothDevice {
     pub is_connected: bool,
     /// デバイスタイプ
     pub device_type: String,
+    /// アドバタイズされているサービスUUID一覧
+    #[serde(default)]
+    pub service_uuids: Vec<String>,
+    /// 24ビットのClass of Device（取得できない場合は0）
+    #[serde(default)]
+    pub class_of_device: u32,
+    /// ボンディング状態
+    #[serde(default)]
+    pub bond_state: BondState,
 }
 
 impl BluetoothDevice {
@@ -31,201 +52,514 @@ impl BluetoothDevice {
             address,
             is_connected: false,
             device_type,
+            service_uuids: Vec::new(),
+            class_of_device: 0,
+            bond_state: BondState::NotBonded,
         }
     }
 }
 
-/// Bluetooth操作を管理するマネージャー
-pub struct BluetoothManager;
+/// ボンディング（ペアリング）状態
+/// AndroidのBtBondState（`BOND_NONE`/`BOND_BONDING`/`BOND_BONDED`）相当
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BondState {
+    /// ペアリングされていない
+    #[default]
+    NotBonded,
+    /// ペアリング処理中
+    Bonding,
+    /// ペアリング済み
+    Bonded,
+}
 
-impl BluetoothManager {
-    /// 新しいBluetoothManagerインスタンスを作成
-    pub fn new() -> Self {
-        Self
+/// Class of Device（CoD）からデバイスタイプを分類する
+/// AndroidのBluetoothClass（`bt_utils::cod`）同様、メジャークラス（bit 8-12）と
+/// マイナークラス（bit 2-7）を見て判定する。CoDが0または未知の場合は"Unknown"を返す
+pub fn classify_device_type(class_of_device: u32) -> String {
+    if class_of_device == 0 {
+        return "Unknown".to_string();
     }
 
-    /// 利用可能なBluetoothデバイス一覧を取得
-    pub fn list_devices(&self) -> Result<Vec<BluetoothDevice>> {
-        println!("Bluetoothデバイスをスキャン中...");
-        
-        let mut devices = Vec::new();
-        
-        // Windowsレジストリからペアリング済みBluetoothデバイス情報を取得
-        let output = std::process::Command::new("reg")
-            .args(&[
-                "query",
-                "HKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\BTHPORT\\Parameters\\Devices",
-                "/s"
-            ])
-            .output()
-            .context("レジストリクエリの実行に失敗しました")?;
-        
-        if !output.status.success() {
-            println!("Bluetoothデバイス情報の取得に失敗しました");
-            return Ok(devices);
-        }
-        
-        let registry_output = String::from_utf8_lossy(&output.stdout);
-        devices = self.parse_bluetooth_registry(&registry_output)?;
-        
-        println!("{}個のBluetoothデバイスが見つかりました", devices.len());
-        Ok(devices)
-    }
+    let major = (class_of_device >> 8) & 0x1F;
+    let minor = (class_of_device >> 2) & 0x3F;
 
-    /// 指定されたデバイスに接続
-    pub fn connect_device(&self, address: &str) -> Result<()> {
-        // モック実装: 実際のWindows Bluetooth APIは後で実装
-        println!("デバイス {} に接続を試行中...", address);
-        
-        // MACアドレスの形式を簡単にチェック
-        if !self.is_valid_mac_address(address) {
-            return Err(anyhow::anyhow!("無効なMACアドレス形式です: {}", address));
+    match major {
+        0x01 => "Computer".to_string(),
+        0x02 => "Phone".to_string(),
+        0x04 => match minor {
+            0x01 => "Headset".to_string(),
+            0x02 => "Handsfree".to_string(),
+            0x06 => "Headphones".to_string(),
+            0x14 => "Speaker".to_string(),
+            _ => "Audio/Video".to_string(),
+        },
+        0x05 => {
+            let is_keyboard = class_of_device & 0x40 != 0;
+            let is_pointing = class_of_device & 0x80 != 0;
+
+            // 両方のビットが立っている場合はAndroidのis_cod_hid_combo同様、複合デバイスとして扱う
+            if is_keyboard && is_pointing {
+                "Keyboard/Pointing Combo".to_string()
+            } else if is_keyboard {
+                "Keyboard".to_string()
+            } else if is_pointing {
+                "Pointing Device".to_string()
+            } else {
+                "Peripheral".to_string()
+            }
         }
-        
-        // 接続シミュレーション
-        std::thread::sleep(std::time::Duration::from_millis(500));
-        println!("デバイス {} に正常に接続しました", address);
-        Ok(())
+        0x06 => "Imaging".to_string(),
+        _ => "Unknown".to_string(),
     }
+}
 
-    /// 指定されたデバイスから切断
-    pub fn disconnect_device(&self, address: &str) -> Result<()> {
-        // モック実装: 実際のWindows Bluetooth APIは後で実装
-        println!("デバイス {} から切断中...", address);
-        
-        // MACアドレスの形式を簡単にチェック
-        if !self.is_valid_mac_address(address) {
-            return Err(anyhow::anyhow!("無効なMACアドレス形式です: {}", address));
+/// スキャンフィルタ1件分の条件（フィールド間はAND）
+/// WebBluetoothのscan filterと同様に、指定されたフィールドすべてを満たす場合にマッチする
+#[derive(Debug, Clone, Default)]
+pub struct BluetoothScanFilter {
+    /// デバイス名の完全一致条件
+    pub name: Option<String>,
+    /// デバイス名の前方一致条件
+    pub name_prefix: Option<String>,
+    /// いずれかを広告していればマッチするサービスUUID一覧
+    pub services: Vec<String>,
+    /// デバイスタイプの一致条件
+    pub device_type: Option<String>,
+}
+
+impl BluetoothScanFilter {
+    /// このフィルタの全条件をデバイスが満たすかどうかを判定
+    pub fn matches(&self, device: &BluetoothDevice) -> bool {
+        if let Some(name) = &self.name {
+            if device.name != *name {
+                return false;
+            }
         }
-        
-        // 切断シミュレーション
-        std::thread::sleep(std::time::Duration::from_millis(300));
-        println!("デバイス {} から正常に切断しました", address);
-        Ok(())
-    }
-    
-    /// MACアドレスの形式をチェック
-    fn is_valid_mac_address(&self, address: &str) -> bool {
-        // 簡単な形式チェック: XX:XX:XX:XX:XX:XX
-        let parts: Vec<&str> = address.split(':').collect();
-        if parts.len() != 6 {
-            return false;
+
+        if let Some(prefix) = &self.name_prefix {
+            if !device.name.starts_with(prefix.as_str()) {
+                return false;
+            }
         }
-        
-        for part in parts {
-            if part.len() != 2 {
+
+        if !self.services.is_empty() {
+            let advertises_any = self.services.iter()
+                .any(|uuid| device.service_uuids.iter().any(|advertised| advertised.eq_ignore_ascii_case(uuid)));
+            if !advertises_any {
                 return false;
             }
-            if !part.chars().all(|c| c.is_ascii_hexdigit()) {
+        }
+
+        if let Some(device_type) = &self.device_type {
+            if !device.device_type.eq_ignore_ascii_case(device_type) {
                 return false;
             }
         }
-        
+
         true
     }
-    
-    /// レジストリ出力を解析してBluetoothデバイス一覧を作成
-    fn parse_bluetooth_registry(&self, registry_output: &str) -> Result<Vec<BluetoothDevice>> {
-        let mut devices = Vec::new();
-        let lines: Vec<&str> = registry_output.lines().collect();
-        
-        let mut current_device_key: Option<String> = None;
-        let mut current_device_name: Option<String> = None;
-        let mut current_device_connected = false;
-        
-        for line in lines {
-            let line = line.trim();
-            
-            // デバイスキー（MACアドレス）を検出
-            if line.starts_with("HKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\BTHPORT\\Parameters\\Devices\\") {
-                // 前のデバイス情報を保存
-                if let Some(device_key) = current_device_key.take() {
-                    if let Some(mac_address) = self.format_mac_address(&device_key) {
-                        let device_name = current_device_name.take().unwrap_or_else(|| {
-                            format!("Bluetooth Device {}", &mac_address[..8])
-                        });
-                        
-                        devices.push(BluetoothDevice {
-                            name: device_name.clone(),
-                            address: mac_address.clone(),
-                            is_connected: current_device_connected,
-                            device_type: self.determine_device_type_from_name(&device_name),
-                        });
-                    }
+}
+
+/// フィルタのOR列。空の場合は常にマッチする
+#[derive(Debug, Clone, Default)]
+pub struct BluetoothScanFilterSequence(pub Vec<BluetoothScanFilter>);
+
+impl BluetoothScanFilterSequence {
+    /// シーケンス中のいずれかのフィルタにマッチするかどうかを判定
+    pub fn matches(&self, device: &BluetoothDevice) -> bool {
+        if self.0.is_empty() {
+            return true;
+        }
+
+        self.0.iter().any(|filter| filter.matches(device))
+    }
+}
+
+/// ブロックリストに含まれるUUID・アドレスを常に除外する
+pub fn is_blocklisted(device: &BluetoothDevice, blocklist: &[String]) -> bool {
+    blocklist.iter().any(|entry| {
+        entry.eq_ignore_ascii_case(&device.address)
+            || device.service_uuids.iter().any(|uuid| uuid.eq_ignore_ascii_case(entry))
+    })
+}
+
+/// デバイスの接続情報（信号強度・送信電力）
+/// Chromiumの`BluetoothDevice::ConnectionInfo`にならい、取得できない値は`None`とする
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// 受信信号強度（RSSI、dBm）
+    pub rssi: Option<i16>,
+    /// 現在の送信電力（dBm）
+    pub transmit_power: Option<i16>,
+    /// 到達可能な最大送信電力（dBm）
+    pub max_transmit_power: Option<i16>,
+}
+
+/// Bluetooth操作のバックエンドを抽象化するトレイト
+/// 実機（Windows/Linux）と、CI/テスト用のモック実装を差し替え可能にする
+/// `DiscoverySession`がバックグラウンドスレッドからポーリングできるよう`Send`を要求する
+pub trait BluetoothBackend: Send {
+    /// 利用可能なBluetoothデバイス一覧を取得
+    fn list_devices(&mut self) -> Result<Vec<BluetoothDevice>>;
+    /// 指定されたデバイスに接続
+    fn connect_device(&mut self, address: &str) -> Result<()>;
+    /// 指定されたデバイスから切断
+    fn disconnect_device(&mut self, address: &str) -> Result<()>;
+    /// 指定されたデバイスが接続済みかどうかを確認
+    fn is_connected(&self, address: &str) -> Result<bool>;
+    /// 指定されたデバイスの接続情報（RSSI・送信電力）を取得する
+    fn connection_info(&self, address: &str) -> Result<ConnectionInfo>;
+    /// 指定されたデバイスとペアリング（ボンディング）する
+    fn pair(&mut self, address: &str) -> Result<()>;
+    /// 指定されたデバイスのボンディングを解除する
+    fn remove_bond(&mut self, address: &str) -> Result<()>;
+}
+
+/// モックバックエンド用のフィクスチャファイル（TOML）のスキーマ
+#[derive(Debug, Deserialize)]
+struct MockFixture {
+    /// フィクスチャに含まれるデバイス一覧
+    devices: Vec<BluetoothDevice>,
+}
+
+/// CI・テスト向けのモックバックエンド
+/// TOMLフィクスチャからデバイス一覧を読み込み、接続状態をメモリ上で変更する
+/// Servo/Chromiumのfake Bluetooth device clientのように、呼び出し履歴を記録して
+/// テストから接続試行の回数・対象アドレスを検証できるようにする
+pub struct MockBackend {
+    devices: Vec<BluetoothDevice>,
+    connect_calls: Vec<String>,
+    disconnect_calls: Vec<String>,
+    connection_info: HashMap<String, ConnectionInfo>,
+}
+
+impl MockBackend {
+    /// TOMLフィクスチャファイルからMockBackendを構築
+    pub fn from_fixture<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("モックフィクスチャ {:?} の読み込みに失敗しました", path.as_ref()))?;
+
+        let fixture: MockFixture = toml::from_str(&content)
+            .with_context(|| "モックフィクスチャの解析に失敗しました")?;
+
+        Ok(Self::with_devices(fixture.devices))
+    }
+
+    /// デバイス一覧を直接指定してMockBackendを構築
+    pub fn with_devices(devices: Vec<BluetoothDevice>) -> Self {
+        Self {
+            devices,
+            connect_calls: Vec::new(),
+            disconnect_calls: Vec::new(),
+            connection_info: HashMap::new(),
+        }
+    }
+
+    /// `connect_device`が呼ばれたアドレスの履歴（呼び出し順）
+    pub fn connect_calls(&self) -> &[String] {
+        &self.connect_calls
+    }
+
+    /// `disconnect_device`が呼ばれたアドレスの履歴（呼び出し順）
+    pub fn disconnect_calls(&self) -> &[String] {
+        &self.disconnect_calls
+    }
+
+    /// 指定アドレスの接続情報を設定する（テスト用）
+    pub fn set_connection_info(&mut self, address: &str, info: ConnectionInfo) {
+        self.connection_info.insert(address.to_string(), info);
+    }
+}
+
+impl BluetoothBackend for MockBackend {
+    fn list_devices(&mut self) -> Result<Vec<BluetoothDevice>> {
+        Ok(self.devices.clone())
+    }
+
+    fn connect_device(&mut self, address: &str) -> Result<()> {
+        self.connect_calls.push(address.to_string());
+
+        let device = self.devices.iter_mut().find(|d| d.address == address)
+            .ok_or_else(|| anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address))?;
+        device.is_connected = true;
+        Ok(())
+    }
+
+    fn disconnect_device(&mut self, address: &str) -> Result<()> {
+        self.disconnect_calls.push(address.to_string());
+
+        let device = self.devices.iter_mut().find(|d| d.address == address)
+            .ok_or_else(|| anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address))?;
+        device.is_connected = false;
+        Ok(())
+    }
+
+    fn is_connected(&self, address: &str) -> Result<bool> {
+        Ok(self.devices.iter().find(|d| d.address == address)
+            .map(|d| d.is_connected)
+            .unwrap_or(false))
+    }
+
+    fn connection_info(&self, address: &str) -> Result<ConnectionInfo> {
+        self.devices.iter().find(|d| d.address == address)
+            .ok_or_else(|| anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address))?;
+        Ok(self.connection_info.get(address).copied().unwrap_or_default())
+    }
+
+    fn pair(&mut self, address: &str) -> Result<()> {
+        let device = self.devices.iter_mut().find(|d| d.address == address)
+            .ok_or_else(|| anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address))?;
+        device.bond_state = BondState::Bonded;
+        Ok(())
+    }
+
+    fn remove_bond(&mut self, address: &str) -> Result<()> {
+        let device = self.devices.iter_mut().find(|d| d.address == address)
+            .ok_or_else(|| anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address))?;
+        device.bond_state = BondState::NotBonded;
+        Ok(())
+    }
+}
+
+/// Chromiumのfake Bluetoothアダプタにならい、約500ms間隔でデバイス一覧をポーリングする
+const DISCOVERY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Chromium Classicデバイスのinquiry timeoutにならった既定値（約3分）
+const DEFAULT_INQUIRY_TIMEOUT: Duration = Duration::from_secs(180);
+/// GATTのトランザクションタイムアウト仕様（30秒）にならった、非同期APIの既定オペレーションタイムアウト
+const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 同期的なバックエンド呼び出しをワーカースレッドに逃がし、`operation_timeout`で呼び出し元への応答を打ち切る
+/// GUI/イベントループをブロックしないよう、非同期APIの内部実装で共通に使う
+///
+/// 注意: ここで打ち切れるのは「呼び出し元が待つ時間」だけで、スポーンされた`spawn_blocking`タスク自体は
+/// バックエンドの呼び出しが返るまで動き続ける。バックエンドは`Arc<Mutex<_>>`で共有されているため、
+/// そのタスクが掴んだロックは解放されず、タイムアウト後に発行した次の（同期・非同期を問わない）
+/// マネージャー呼び出しは、ハングした元の呼び出しが終わるまでロック待ちでブロックしうる
+async fn run_with_timeout<T, F>(operation_timeout: Duration, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    match tokio::time::timeout(operation_timeout, tokio::task::spawn_blocking(f)).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(join_error)) => Err(anyhow::anyhow!("バックエンド呼び出しのタスクが失敗しました: {}", join_error)),
+        Err(_) => Err(anyhow::anyhow!("操作がタイムアウトしました（{:?}）", operation_timeout)),
+    }
+}
+
+/// ディスカバリーセッションが`DiscoverySession`越しに通知するイベント
+#[derive(Debug, Clone)]
+pub enum DiscoveryEvent {
+    /// 新しく発見されたデバイス
+    DeviceFound(BluetoothDevice),
+    /// `inquiry_timeout`の間再発見されなかったデバイス
+    DeviceLost(BluetoothDevice),
+}
+
+/// 実行中のディスカバリーセッション
+/// バックグラウンドスレッドが`DISCOVERY_POLL_INTERVAL`間隔で`list_devices`をポーリングし、
+/// 新規デバイスの出現と`inquiry_timeout`の間再発見できなかったデバイスの消失をチャネル経由で通知する
+/// `Drop`時にバックグラウンドスレッドを停止し、joinして後始末する
+pub struct DiscoverySession {
+    events: mpsc::Receiver<DiscoveryEvent>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DiscoverySession {
+    /// `DeviceFound`/`DeviceLost`イベントを受信するチャネルの受信側
+    pub fn events(&self) -> &mpsc::Receiver<DiscoveryEvent> {
+        &self.events
+    }
+}
+
+impl Drop for DiscoverySession {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Bluetooth操作を管理するマネージャー
+pub struct BluetoothManager {
+    backend: Arc<Mutex<Box<dyn BluetoothBackend>>>,
+}
+
+impl BluetoothManager {
+    /// 新しいBluetoothManagerインスタンスを作成
+    /// 環境変数 `QCBD_MOCK_FIXTURE` にフィクスチャのパスが設定されている場合はモックバックエンドを使用する
+    pub fn new() -> Self {
+        let backend: Box<dyn BluetoothBackend> = match std::env::var("QCBD_MOCK_FIXTURE") {
+            Ok(path) => match MockBackend::from_fixture(&path) {
+                Ok(mock) => Box::new(mock),
+                Err(e) => {
+                    eprintln!("モックフィクスチャの読み込みに失敗しました: {}", e);
+                    Self::default_backend()
                 }
-                
-                // 新しいデバイスキーを抽出
-                if let Some(key_start) = line.rfind('\\') {
-                    current_device_key = Some(line[key_start + 1..].to_string());
-                    current_device_name = None;
-                    current_device_connected = false;
+            },
+            Err(_) => Self::default_backend(),
+        };
+
+        Self { backend: Arc::new(Mutex::new(backend)) }
+    }
+
+    /// 現在のプラットフォーム向けの実バックエンドを作成
+    #[cfg(target_os = "windows")]
+    fn default_backend() -> Box<dyn BluetoothBackend> {
+        Box::new(WindowsBackend::new())
+    }
+
+    /// 現在のプラットフォーム向けの実バックエンドを作成
+    #[cfg(target_os = "linux")]
+    fn default_backend() -> Box<dyn BluetoothBackend> {
+        Box::new(LinuxBackend::new())
+    }
+
+    /// 任意のバックエンドを指定してBluetoothManagerを構築
+    pub fn with_backend(backend: Box<dyn BluetoothBackend>) -> Self {
+        Self { backend: Arc::new(Mutex::new(backend)) }
+    }
+
+    /// モックフィクスチャファイルを指定してBluetoothManagerを構築
+    pub fn with_mock_fixture<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::with_backend(Box::new(MockBackend::from_fixture(path)?)))
+    }
+
+    /// 利用可能なBluetoothデバイス一覧を取得
+    pub fn list_devices(&mut self) -> Result<Vec<BluetoothDevice>> {
+        self.backend.lock().unwrap().list_devices()
+    }
+
+    /// スキャンフィルタ列のいずれかにマッチするデバイスのみを取得
+    /// 混雑したペアリング一覧を、オーディオ機器や既知の名前プレフィックスだけに絞り込む用途を想定
+    pub fn list_devices_filtered(&mut self, filters: &BluetoothScanFilterSequence) -> Result<Vec<BluetoothDevice>> {
+        Ok(self.backend.lock().unwrap().list_devices()?
+            .into_iter()
+            .filter(|device| filters.matches(device))
+            .collect())
+    }
+
+    /// 指定されたデバイスに接続
+    pub fn connect_device(&mut self, address: &str) -> Result<()> {
+        self.backend.lock().unwrap().connect_device(address)
+    }
+
+    /// 指定されたデバイスから切断
+    pub fn disconnect_device(&mut self, address: &str) -> Result<()> {
+        self.backend.lock().unwrap().disconnect_device(address)
+    }
+
+    /// 指定されたデバイスが接続済みかどうかを確認
+    pub fn is_connected(&self, address: &str) -> Result<bool> {
+        self.backend.lock().unwrap().is_connected(address)
+    }
+
+    /// 指定されたデバイスの接続情報（RSSI・送信電力）を取得する
+    /// 複数のペアリング済みヘッドホンのうち最も近いものを選ぶ、といった用途を想定
+    pub fn connection_info(&self, address: &str) -> Result<ConnectionInfo> {
+        self.backend.lock().unwrap().connection_info(address)
+    }
+
+    /// 利用可能なBluetoothデバイス一覧を非同期に取得する（既定タイムアウト）
+    /// ブロッキングする同期APIがGUI/イベントループを止めてしまうのを避けるための非同期窓口
+    /// （`run_with_timeout`の注意点のとおり、ハング時は後続のマネージャー呼び出しまでは保護しない）
+    pub async fn list_devices_async(&self) -> Result<Vec<BluetoothDevice>> {
+        self.list_devices_async_with_timeout(DEFAULT_OPERATION_TIMEOUT).await
+    }
+
+    /// タイムアウトを指定して、利用可能なBluetoothデバイス一覧を非同期に取得する
+    pub async fn list_devices_async_with_timeout(&self, operation_timeout: Duration) -> Result<Vec<BluetoothDevice>> {
+        let backend = Arc::clone(&self.backend);
+        run_with_timeout(operation_timeout, move || backend.lock().unwrap().list_devices()).await
+    }
+
+    /// 指定されたデバイスに非同期に接続する（既定タイムアウト）
+    /// GATTのトランザクションタイムアウト仕様（30秒）にならい、タイムアウト時はエラーを返す
+    pub async fn connect_device_async(&self, address: &str) -> Result<()> {
+        self.connect_device_async_with_timeout(address, DEFAULT_OPERATION_TIMEOUT).await
+    }
+
+    /// タイムアウトを指定して、指定されたデバイスに非同期に接続する
+    pub async fn connect_device_async_with_timeout(&self, address: &str, operation_timeout: Duration) -> Result<()> {
+        let backend = Arc::clone(&self.backend);
+        let address = address.to_string();
+        run_with_timeout(operation_timeout, move || backend.lock().unwrap().connect_device(&address)).await
+    }
+
+    /// 指定されたデバイスから非同期に切断する（既定タイムアウト）
+    pub async fn disconnect_device_async(&self, address: &str) -> Result<()> {
+        self.disconnect_device_async_with_timeout(address, DEFAULT_OPERATION_TIMEOUT).await
+    }
+
+    /// タイムアウトを指定して、指定されたデバイスから非同期に切断する
+    pub async fn disconnect_device_async_with_timeout(&self, address: &str, operation_timeout: Duration) -> Result<()> {
+        let backend = Arc::clone(&self.backend);
+        let address = address.to_string();
+        run_with_timeout(operation_timeout, move || backend.lock().unwrap().disconnect_device(&address)).await
+    }
+
+    /// 指定されたデバイスとペアリング（ボンディング）する
+    pub fn pair(&mut self, address: &str) -> Result<()> {
+        self.backend.lock().unwrap().pair(address)
+    }
+
+    /// 指定されたデバイスのボンディングを解除する
+    pub fn remove_bond(&mut self, address: &str) -> Result<()> {
+        self.backend.lock().unwrap().remove_bond(address)
+    }
+
+    /// デバイスの発見・消失を監視するディスカバリーセッションを開始する
+    /// inquiry timeoutにはChromium Classicデバイス向けの既定値（約3分）を使う
+    pub fn start_discovery(&self) -> DiscoverySession {
+        self.start_discovery_with_timeout(DEFAULT_INQUIRY_TIMEOUT)
+    }
+
+    /// inquiry timeoutを指定してディスカバリーセッションを開始する
+    pub fn start_discovery_with_timeout(&self, inquiry_timeout: Duration) -> DiscoverySession {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let backend = Arc::clone(&self.backend);
+
+        let handle = thread::spawn(move || {
+            let mut last_seen: HashMap<String, (BluetoothDevice, Instant)> = HashMap::new();
+
+            while !thread_stop.load(Ordering::SeqCst) {
+                let devices = backend.lock().unwrap().list_devices().unwrap_or_default();
+                let now = Instant::now();
+
+                for device in &devices {
+                    if !last_seen.contains_key(&device.address)
+                        && sender.send(DiscoveryEvent::DeviceFound(device.clone())).is_err()
+                    {
+                        return;
+                    }
+                    last_seen.insert(device.address.clone(), (device.clone(), now));
                 }
-            }
-            // FriendlyNameを検出（デバイス名）
-            else if line.contains("FriendlyName") && line.contains("REG_SZ") {
-                if let Some(name_start) = line.find("REG_SZ") {
-                    let name_part = &line[name_start + 6..].trim();
-                    if !name_part.is_empty() {
-                        current_device_name = Some(name_part.to_string());
+
+                let lost_addresses: Vec<String> = last_seen.iter()
+                    .filter(|(_, (_, seen_at))| now.duration_since(*seen_at) >= inquiry_timeout)
+                    .map(|(address, _)| address.clone())
+                    .collect();
+
+                for address in lost_addresses {
+                    if let Some((device, _)) = last_seen.remove(&address) {
+                        if sender.send(DiscoveryEvent::DeviceLost(device)).is_err() {
+                            return;
+                        }
                     }
                 }
+
+                thread::sleep(DISCOVERY_POLL_INTERVAL);
             }
-            // LastConnectedを検出（接続状態の推定）
-            else if line.contains("LastConnected") && line.contains("REG_QWORD") {
-                // 最近接続されたデバイスは接続済みと仮定（簡易実装）
-                current_device_connected = true;
-            }
-        }
-        
-        // 最後のデバイス情報を保存
-        if let Some(device_key) = current_device_key {
-            if let Some(mac_address) = self.format_mac_address(&device_key) {
-                let device_name = current_device_name.unwrap_or_else(|| {
-                    format!("Bluetooth Device {}", &mac_address[..8])
-                });
-                
-                devices.push(BluetoothDevice {
-                    name: device_name.clone(),
-                    address: mac_address.clone(),
-                    is_connected: current_device_connected,
-                    device_type: self.determine_device_type_from_name(&device_name),
-                });
-            }
-        }
-        
-        Ok(devices)
-    }
-    
-    /// レジストリキー（MACアドレス）を標準形式に変換
-    fn format_mac_address(&self, registry_key: &str) -> Option<String> {
-        if registry_key.len() != 12 {
-            return None;
-        }
-        
-        let mut formatted = String::new();
-        for (i, c) in registry_key.chars().enumerate() {
-            if i > 0 && i % 2 == 0 {
-                formatted.push(':');
-            }
-            formatted.push(c.to_ascii_uppercase());
-        }
-        
-        Some(formatted)
-    }
-    
-    /// デバイス名からデバイスタイプを推定
-    fn determine_device_type_from_name(&self, device_name: &str) -> String {
-        let name_lower = device_name.to_lowercase();
-        
-        if name_lower.contains("mouse") || name_lower.contains("keyboard") {
-            "Peripheral".to_string()
-        } else if name_lower.contains("headphone") || name_lower.contains("speaker") || 
-                  name_lower.contains("audio") || name_lower.contains("hl7bt") {
-            "Audio/Video".to_string()
-        } else if name_lower.contains("phone") || name_lower.contains("mobile") {
-            "Phone".to_string()
-        } else {
-            "Unknown".to_string()
+        });
+
+        DiscoverySession {
+            events: receiver,
+            stop,
+            handle: Some(handle),
         }
     }
 }
@@ -239,6 +573,90 @@ impl Default for BluetoothManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
+    use tokio::runtime::Runtime;
+
+    fn write_fixture(contents: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("test_fixture_{}_{}.toml", std::process::id(), contents.len()));
+        fs::write(&path, contents).expect("フィクスチャの書き込みに失敗しました");
+        path
+    }
+
+    fn cleanup(path: &PathBuf) {
+        if path.exists() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// `MockBackend`を包み、全呼び出しの前に`delay`だけ`thread::sleep`するテスト専用バックエンド
+    /// タイムアウトのテストでは、実際のバックエンド呼び出しが`operation_timeout`より確実に長くかかる
+    /// 状況を作る必要があるため、ナノ秒オーダーのタイムアウトとスケジューラのブレで偶然成功する
+    /// `MockBackend`単体より、このバックエンドで検証する
+    struct SlowBackend {
+        inner: MockBackend,
+        delay: Duration,
+    }
+
+    impl BluetoothBackend for SlowBackend {
+        fn list_devices(&mut self) -> Result<Vec<BluetoothDevice>> {
+            thread::sleep(self.delay);
+            self.inner.list_devices()
+        }
+
+        fn connect_device(&mut self, address: &str) -> Result<()> {
+            thread::sleep(self.delay);
+            self.inner.connect_device(address)
+        }
+
+        fn disconnect_device(&mut self, address: &str) -> Result<()> {
+            thread::sleep(self.delay);
+            self.inner.disconnect_device(address)
+        }
+
+        fn is_connected(&self, address: &str) -> Result<bool> {
+            thread::sleep(self.delay);
+            self.inner.is_connected(address)
+        }
+
+        fn connection_info(&self, address: &str) -> Result<ConnectionInfo> {
+            thread::sleep(self.delay);
+            self.inner.connection_info(address)
+        }
+
+        fn pair(&mut self, address: &str) -> Result<()> {
+            thread::sleep(self.delay);
+            self.inner.pair(address)
+        }
+
+        fn remove_bond(&mut self, address: &str) -> Result<()> {
+            thread::sleep(self.delay);
+            self.inner.remove_bond(address)
+        }
+    }
+
+    fn sample_devices() -> Vec<BluetoothDevice> {
+        vec![
+            BluetoothDevice {
+                name: "Test Headset".to_string(),
+                address: "AA:BB:CC:DD:EE:FF".to_string(),
+                is_connected: false,
+                device_type: "Audio/Video".to_string(),
+                service_uuids: vec!["0000110b-0000-1000-8000-00805f9b34fb".to_string()],
+                class_of_device: 0x240404,
+                bond_state: BondState::Bonded,
+            },
+            BluetoothDevice {
+                name: "Test Mouse".to_string(),
+                address: "11:22:33:44:55:66".to_string(),
+                is_connected: true,
+                device_type: "Peripheral".to_string(),
+                service_uuids: Vec::new(),
+                class_of_device: 0x2540,
+                bond_state: BondState::NotBonded,
+            },
+        ]
+    }
 
     #[test]
     fn test_bluetooth_device_creation() {
@@ -247,8 +665,11 @@ mod tests {
             address: "AA:BB:CC:DD:EE:FF".to_string(),
             is_connected: false,
             device_type: "Test".to_string(),
+            service_uuids: Vec::new(),
+            class_of_device: 0,
+            bond_state: BondState::NotBonded,
         };
-        
+
         assert_eq!(device.name, "Test Device");
         assert_eq!(device.address, "AA:BB:CC:DD:EE:FF");
         assert!(!device.is_connected);
@@ -256,74 +677,278 @@ mod tests {
     }
 
     #[test]
-    fn test_bluetooth_manager_creation() {
-        let manager = BluetoothManager::new();
-        // マネージャーが正常に作成されることを確認
-        assert!(true); // 基本的な作成テスト
+    fn test_mock_backend_list_devices() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+        let devices = backend.list_devices().expect("list_devicesに失敗しました");
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "Test Headset");
     }
 
     #[test]
-    fn test_list_devices() {
-        let manager = BluetoothManager::new();
-        let result = manager.list_devices();
-        
-        // 実際の実装では常に成功し、実際のBluetoothデバイスを返す
-        assert!(result.is_ok());
-        let devices = result.unwrap();
-        // デバイス数は環境によって異なるため、0個以上であることを確認
-        assert!(devices.len() >= 0);
+    fn test_mock_backend_connect_and_disconnect() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+
+        assert!(!backend.is_connected("AA:BB:CC:DD:EE:FF").unwrap());
+        backend.connect_device("AA:BB:CC:DD:EE:FF").expect("接続に失敗しました");
+        assert!(backend.is_connected("AA:BB:CC:DD:EE:FF").unwrap());
+
+        backend.disconnect_device("AA:BB:CC:DD:EE:FF").expect("切断に失敗しました");
+        assert!(!backend.is_connected("AA:BB:CC:DD:EE:FF").unwrap());
     }
 
     #[test]
-    fn test_valid_mac_address() {
-        let manager = BluetoothManager::new();
-        
-        // 有効なMACアドレス
-        assert!(manager.is_valid_mac_address("AA:BB:CC:DD:EE:FF"));
-        assert!(manager.is_valid_mac_address("00:11:22:33:44:55"));
-        assert!(manager.is_valid_mac_address("FF:FF:FF:FF:FF:FF"));
-        
-        // 無効なMACアドレス
-        assert!(!manager.is_valid_mac_address("AA:BB:CC:DD:EE"));     // 短い
-        assert!(!manager.is_valid_mac_address("AA:BB:CC:DD:EE:FF:GG")); // 長い
-        assert!(!manager.is_valid_mac_address("AA-BB-CC-DD-EE-FF"));   // 区切り文字が違う
-        assert!(!manager.is_valid_mac_address("GG:HH:II:JJ:KK:LL"));   // 無効な16進数
-        assert!(!manager.is_valid_mac_address(""));                    // 空文字
+    fn test_mock_backend_records_connect_and_disconnect_calls() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+
+        backend.connect_device("AA:BB:CC:DD:EE:FF").expect("接続に失敗しました");
+        backend.connect_device("00:00:00:00:00:00").ok();
+        backend.disconnect_device("AA:BB:CC:DD:EE:FF").expect("切断に失敗しました");
+
+        assert_eq!(backend.connect_calls(), &["AA:BB:CC:DD:EE:FF", "00:00:00:00:00:00"]);
+        assert_eq!(backend.disconnect_calls(), &["AA:BB:CC:DD:EE:FF"]);
     }
 
     #[test]
-    fn test_connect_device_with_valid_address() {
-        let manager = BluetoothManager::new();
-        let result = manager.connect_device("AA:BB:CC:DD:EE:FF");
-        
-        // モック実装では有効なMACアドレスで成功する
-        assert!(result.is_ok());
+    fn test_mock_backend_connect_unknown_device() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+        let result = backend.connect_device("00:00:00:00:00:00");
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_connect_device_with_invalid_address() {
-        let manager = BluetoothManager::new();
-        let result = manager.connect_device("invalid-address");
-        
-        // 無効なMACアドレスではエラーになる
+    fn test_mock_backend_pair_and_remove_bond() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+
+        backend.pair("11:22:33:44:55:66").expect("ペアリングに失敗しました");
+        let devices = backend.list_devices().expect("list_devicesに失敗しました");
+        assert_eq!(devices[1].bond_state, BondState::Bonded);
+
+        backend.remove_bond("11:22:33:44:55:66").expect("ボンディング解除に失敗しました");
+        let devices = backend.list_devices().expect("list_devicesに失敗しました");
+        assert_eq!(devices[1].bond_state, BondState::NotBonded);
+    }
+
+    #[test]
+    fn test_mock_backend_pair_unknown_device() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+        let result = backend.pair("00:00:00:00:00:00");
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_disconnect_device_with_valid_address() {
-        let manager = BluetoothManager::new();
-        let result = manager.disconnect_device("AA:BB:CC:DD:EE:FF");
-        
-        // モック実装では有効なMACアドレスで成功する
-        assert!(result.is_ok());
+    fn test_bluetooth_manager_list_devices_filtered() {
+        let mut manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(sample_devices())));
+
+        let sequence = BluetoothScanFilterSequence(vec![
+            BluetoothScanFilter { device_type: Some("Audio/Video".to_string()), ..Default::default() },
+        ]);
+
+        let devices = manager.list_devices_filtered(&sequence).expect("list_devices_filteredに失敗しました");
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "Test Headset");
     }
 
     #[test]
-    fn test_disconnect_device_with_invalid_address() {
-        let manager = BluetoothManager::new();
-        let result = manager.disconnect_device("invalid-address");
-        
-        // 無効なMACアドレスではエラーになる
+    fn test_discovery_session_emits_device_found_for_each_device() {
+        let manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(sample_devices())));
+        let session = manager.start_discovery();
+
+        let mut found_addresses = Vec::new();
+        for _ in 0..sample_devices().len() {
+            match session.events().recv_timeout(Duration::from_secs(2)) {
+                Ok(DiscoveryEvent::DeviceFound(device)) => found_addresses.push(device.address),
+                other => panic!("DeviceFoundイベントを期待しましたが異なる結果でした: {:?}", other),
+            }
+        }
+
+        found_addresses.sort();
+        let mut expected_addresses: Vec<String> = sample_devices().iter().map(|d| d.address.clone()).collect();
+        expected_addresses.sort();
+        assert_eq!(found_addresses, expected_addresses);
+    }
+
+    #[test]
+    fn test_bluetooth_manager_connection_info() {
+        let mut backend = MockBackend::with_devices(sample_devices());
+        backend.set_connection_info("AA:BB:CC:DD:EE:FF", ConnectionInfo {
+            rssi: Some(-40),
+            transmit_power: Some(4),
+            max_transmit_power: Some(8),
+        });
+        let manager = BluetoothManager::with_backend(Box::new(backend));
+
+        let info = manager.connection_info("AA:BB:CC:DD:EE:FF").expect("connection_infoに失敗しました");
+        assert_eq!(info.rssi, Some(-40));
+        assert_eq!(info.transmit_power, Some(4));
+        assert_eq!(info.max_transmit_power, Some(8));
+
+        // 値を設定していないデバイスは不明（None）として返る
+        let unknown = manager.connection_info("11:22:33:44:55:66").expect("connection_infoに失敗しました");
+        assert_eq!(unknown, ConnectionInfo::default());
+    }
+
+    #[test]
+    fn test_bluetooth_manager_async_list_and_connect() {
+        let manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(sample_devices())));
+        let runtime = Runtime::new().expect("tokioランタイムの作成に失敗しました");
+
+        let devices = runtime.block_on(manager.list_devices_async())
+            .expect("list_devices_asyncに失敗しました");
+        assert_eq!(devices.len(), sample_devices().len());
+
+        runtime.block_on(manager.connect_device_async("AA:BB:CC:DD:EE:FF"))
+            .expect("connect_device_asyncに失敗しました");
+        assert!(manager.is_connected("AA:BB:CC:DD:EE:FF").unwrap_or(false));
+    }
+
+    #[test]
+    fn test_bluetooth_manager_connect_device_async_times_out() {
+        // バックエンド呼び出しがタイムアウトより確実に長くかかるようにし、
+        // ナノ秒オーダーのタイムアウトとスケジューラのブレに依存しないようにする
+        let backend = SlowBackend {
+            inner: MockBackend::with_devices(sample_devices()),
+            delay: Duration::from_millis(200),
+        };
+        let manager = BluetoothManager::with_backend(Box::new(backend));
+        let runtime = Runtime::new().expect("tokioランタイムの作成に失敗しました");
+
+        let result = runtime.block_on(
+            manager.connect_device_async_with_timeout("AA:BB:CC:DD:EE:FF", Duration::from_millis(10)),
+        );
+
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bluetooth_manager_with_mock_fixture() {
+        let fixture_path = write_fixture(
+            r#"
+            [[devices]]
+            name = "Fixture Headset"
+            address = "AA:BB:CC:DD:EE:FF"
+            is_connected = false
+            device_type = "Audio/Video"
+            "#,
+        );
+
+        let mut manager = BluetoothManager::with_mock_fixture(&fixture_path)
+            .expect("モックフィクスチャからのマネージャー構築に失敗しました");
+
+        let devices = manager.list_devices().expect("list_devicesに失敗しました");
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "Fixture Headset");
+
+        manager.connect_device("AA:BB:CC:DD:EE:FF").expect("接続に失敗しました");
+        assert!(manager.is_connected("AA:BB:CC:DD:EE:FF").unwrap());
+
+        cleanup(&fixture_path);
+    }
+
+    #[test]
+    fn test_scan_filter_exact_name() {
+        let filter = BluetoothScanFilter {
+            name: Some("Test Headset".to_string()),
+            ..Default::default()
+        };
+
+        let devices = sample_devices();
+        assert!(filter.matches(&devices[0]));
+        assert!(!filter.matches(&devices[1]));
+    }
+
+    #[test]
+    fn test_scan_filter_name_prefix() {
+        let filter = BluetoothScanFilter {
+            name_prefix: Some("Test Head".to_string()),
+            ..Default::default()
+        };
+
+        let devices = sample_devices();
+        assert!(filter.matches(&devices[0]));
+        assert!(!filter.matches(&devices[1]));
+    }
+
+    #[test]
+    fn test_scan_filter_service_uuid() {
+        let filter = BluetoothScanFilter {
+            services: vec!["0000110B-0000-1000-8000-00805F9B34FB".to_string()],
+            ..Default::default()
+        };
+
+        let devices = sample_devices();
+        assert!(filter.matches(&devices[0]));
+        assert!(!filter.matches(&devices[1]));
+    }
+
+    #[test]
+    fn test_scan_filter_sequence_is_or_of_filters() {
+        let sequence = BluetoothScanFilterSequence(vec![
+            BluetoothScanFilter { device_type: Some("Peripheral".to_string()), ..Default::default() },
+            BluetoothScanFilter { device_type: Some("Audio/Video".to_string()), ..Default::default() },
+        ]);
+
+        let devices = sample_devices();
+        assert!(sequence.matches(&devices[0]));
+        assert!(sequence.matches(&devices[1]));
+    }
+
+    #[test]
+    fn test_scan_filter_sequence_empty_matches_everything() {
+        let sequence = BluetoothScanFilterSequence::default();
+        let devices = sample_devices();
+        assert!(sequence.matches(&devices[0]));
+        assert!(sequence.matches(&devices[1]));
+    }
+
+    #[test]
+    fn test_is_blocklisted() {
+        let devices = sample_devices();
+        let blocklist = vec!["AA:BB:CC:DD:EE:FF".to_string()];
+
+        assert!(is_blocklisted(&devices[0], &blocklist));
+        assert!(!is_blocklisted(&devices[1], &blocklist));
+    }
+
+    #[test]
+    fn test_classify_device_type_unknown_for_zero() {
+        assert_eq!(classify_device_type(0), "Unknown");
+    }
+
+    #[test]
+    fn test_classify_device_type_computer_and_phone() {
+        assert_eq!(classify_device_type(0x000100), "Computer");
+        assert_eq!(classify_device_type(0x000200), "Phone");
+    }
+
+    #[test]
+    fn test_classify_device_type_audio_video_refines_headset_and_handsfree() {
+        assert_eq!(classify_device_type(0x240404), "Headset");
+        assert_eq!(classify_device_type(0x240408), "Handsfree");
+        assert_eq!(classify_device_type(0x240400), "Audio/Video");
+    }
+
+    #[test]
+    fn test_classify_device_type_peripheral_keyboard_and_pointing() {
+        assert_eq!(classify_device_type(0x2540), "Keyboard");
+        assert_eq!(classify_device_type(0x2580), "Pointing Device");
+        assert_eq!(classify_device_type(0x2500), "Peripheral");
+    }
+
+    #[test]
+    fn test_classify_device_type_peripheral_hid_combo() {
+        // キーボード・ポインティングの両ビットが立っている場合はAndroidのis_cod_hid_combo相当の扱い
+        assert_eq!(classify_device_type(0x25C0), "Keyboard/Pointing Combo");
+    }
+
+    #[test]
+    fn test_classify_device_type_audio_video_refines_headphones_and_speaker() {
+        assert_eq!(classify_device_type(0x240418), "Headphones");
+        assert_eq!(classify_device_type(0x240450), "Speaker");
+    }
+
+    #[test]
+    fn test_classify_device_type_imaging() {
+        assert_eq!(classify_device_type(0x240600), "Imaging");
+    }
+}
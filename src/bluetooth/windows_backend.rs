@@ -0,0 +1,371 @@
+//! Windows環境向けのBluetoothバックエンド（Windows Bluetooth API経由）
+
+use anyhow::{Context, Result};
+use windows::{
+    core::*,
+    Win32::Devices::Bluetooth::*,
+    Win32::Foundation::*,
+    Win32::System::Com::*,
+};
+
+use super::{classify_device_type, BluetoothBackend, BluetoothDevice, BondState, ConnectionInfo};
+
+/// `BluetoothSetServiceState`に渡すサービスを有効化するフラグ
+const BLUETOOTH_SERVICE_ENABLE: u32 = 0x01;
+/// `BluetoothSetServiceState`に渡すサービスを無効化するフラグ
+const BLUETOOTH_SERVICE_DISABLE: u32 = 0x00;
+
+/// Human Interface Device サービスクラスUUID（マウス・キーボードなど）
+const SERVICE_CLASS_HID: GUID = GUID::from_u128(0x0000_1124_0000_1000_8000_00805f9b34fb);
+/// Handsfree（音声プロファイル）サービスクラスUUID
+const SERVICE_CLASS_HANDSFREE: GUID = GUID::from_u128(0x0000_111e_0000_1000_8000_00805f9b34fb);
+
+/// Windows環境向けのBluetoothバックエンド
+pub struct WindowsBackend;
+
+impl WindowsBackend {
+    /// 新しいWindowsBackendインスタンスを作成
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// MACアドレスの形式をチェック
+    fn is_valid_mac_address(&self, address: &str) -> bool {
+        // 簡単な形式チェック: XX:XX:XX:XX:XX:XX
+        let parts: Vec<&str> = address.split(':').collect();
+        if parts.len() != 6 {
+            return false;
+        }
+
+        for part in parts {
+            if part.len() != 2 {
+                return false;
+            }
+            if !part.chars().all(|c| c.is_ascii_hexdigit()) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// `BluetoothFindFirstDevice`/`BluetoothFindNextDevice`でデバイス一覧を列挙する
+    /// 認証済み・既知・接続中のデバイスを対象とし、アクティブな問い合わせ（発見）は行わない
+    fn enumerate_devices(&self) -> Result<Vec<BluetoothDevice>> {
+        let mut devices = Vec::new();
+
+        let search_params = BLUETOOTH_DEVICE_SEARCH_PARAMS {
+            dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_SEARCH_PARAMS>() as u32,
+            fReturnAuthenticated: true.into(),
+            fReturnRemembered: true.into(),
+            fReturnUnknown: false.into(),
+            fReturnConnected: true.into(),
+            fIssueInquiry: false.into(),
+            cTimeoutMultiplier: 0,
+            hRadio: HANDLE::default(),
+        };
+
+        let mut device_info = BLUETOOTH_DEVICE_INFO {
+            dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+            ..Default::default()
+        };
+
+        unsafe {
+            let find_handle = BluetoothFindFirstDevice(&search_params, &mut device_info);
+            if find_handle.is_invalid() {
+                // 対象デバイスが1件も見つからない場合もエラーではなく空の一覧として扱う
+                return Ok(devices);
+            }
+
+            loop {
+                devices.push(Self::device_info_to_bluetooth_device(&device_info));
+
+                device_info = BLUETOOTH_DEVICE_INFO {
+                    dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+                    ..Default::default()
+                };
+
+                if !BluetoothFindNextDevice(find_handle, &mut device_info).as_bool() {
+                    break;
+                }
+            }
+
+            let _ = BluetoothFindDeviceClose(find_handle);
+        }
+
+        Ok(devices)
+    }
+
+    /// `BLUETOOTH_DEVICE_INFO`から`BluetoothDevice`を作成する
+    fn device_info_to_bluetooth_device(device_info: &BLUETOOTH_DEVICE_INFO) -> BluetoothDevice {
+        let class_of_device = device_info.ulClassofDevice;
+
+        BluetoothDevice {
+            name: Self::decode_device_name(&device_info.szName),
+            address: Self::format_bluetooth_address(&device_info.Address),
+            is_connected: device_info.fConnected.as_bool(),
+            device_type: classify_device_type(class_of_device),
+            service_uuids: Vec::new(),
+            class_of_device,
+            bond_state: if device_info.fAuthenticated.as_bool() {
+                BondState::Bonded
+            } else {
+                BondState::NotBonded
+            },
+        }
+    }
+
+    /// `szName`（null終端のUTF-16配列）をデバイス名の文字列に変換する
+    fn decode_device_name(sz_name: &[u16]) -> String {
+        let len = sz_name.iter().position(|&c| c == 0).unwrap_or(sz_name.len());
+        String::from_utf16_lossy(&sz_name[..len])
+    }
+
+    /// `BLUETOOTH_ADDRESS`を`XX:XX:XX:XX:XX:XX`形式の文字列に変換する
+    fn format_bluetooth_address(address: &BLUETOOTH_ADDRESS) -> String {
+        let bytes = unsafe { address.Anonymous.rgBytes };
+        // Bluetoothアドレスは下位バイトから格納されているため、表示時は逆順にする
+        bytes.iter().rev()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+
+    /// `XX:XX:XX:XX:XX:XX`形式の文字列を`BLUETOOTH_ADDRESS`に変換する
+    fn parse_bluetooth_address(address: &str) -> Result<BLUETOOTH_ADDRESS> {
+        let parts: Vec<&str> = address.split(':').collect();
+        if parts.len() != 6 {
+            return Err(anyhow::anyhow!("無効なMACアドレス形式です: {}", address));
+        }
+
+        let mut bytes = [0u8; 6];
+        for (i, part) in parts.iter().enumerate() {
+            // 表示は上位バイトから、格納は下位バイトからのため逆順に詰める
+            bytes[5 - i] = u8::from_str_radix(part, 16)
+                .with_context(|| format!("無効なMACアドレス形式です: {}", address))?;
+        }
+
+        Ok(BLUETOOTH_ADDRESS {
+            Anonymous: BLUETOOTH_ADDRESS_0 { rgBytes: bytes },
+        })
+    }
+
+    /// 2つの`BLUETOOTH_ADDRESS`が同一のデバイスを指すか比較する
+    fn addresses_match(a: &BLUETOOTH_ADDRESS, b: &BLUETOOTH_ADDRESS) -> bool {
+        unsafe { a.Anonymous.rgBytes == b.Anonymous.rgBytes }
+    }
+
+    /// 指定したMACアドレスに一致する`BLUETOOTH_DEVICE_INFO`を探す
+    /// `BluetoothSetServiceState`には列挙時点の生の構造体が必要なため、
+    /// `enumerate_devices`とは別に専用の検索を行う
+    fn find_device_info_by_address(&self, address: &str) -> Result<BLUETOOTH_DEVICE_INFO> {
+        let target = Self::parse_bluetooth_address(address)?;
+
+        let search_params = BLUETOOTH_DEVICE_SEARCH_PARAMS {
+            dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_SEARCH_PARAMS>() as u32,
+            fReturnAuthenticated: true.into(),
+            fReturnRemembered: true.into(),
+            fReturnUnknown: false.into(),
+            fReturnConnected: true.into(),
+            fIssueInquiry: false.into(),
+            cTimeoutMultiplier: 0,
+            hRadio: HANDLE::default(),
+        };
+
+        let mut device_info = BLUETOOTH_DEVICE_INFO {
+            dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+            ..Default::default()
+        };
+
+        unsafe {
+            let find_handle = BluetoothFindFirstDevice(&search_params, &mut device_info);
+            if find_handle.is_invalid() {
+                return Err(anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address));
+            }
+
+            loop {
+                if Self::addresses_match(&device_info.Address, &target) {
+                    let _ = BluetoothFindDeviceClose(find_handle);
+                    return Ok(device_info);
+                }
+
+                device_info = BLUETOOTH_DEVICE_INFO {
+                    dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+                    ..Default::default()
+                };
+
+                if !BluetoothFindNextDevice(find_handle, &mut device_info).as_bool() {
+                    break;
+                }
+            }
+
+            let _ = BluetoothFindDeviceClose(find_handle);
+        }
+
+        Err(anyhow::anyhow!("指定されたデバイスが見つかりません: {}", address))
+    }
+
+    /// Class of Deviceから有効化すべきサービスクラスUUIDを判断する
+    /// メジャークラスがAudio/Video（Headset/Handsfree/Headphones/Speakerを含む）の場合はHandsfree、
+    /// それ以外はHIDとして扱う。`classify_device_type`が返す文字列はマイナークラスまで細分化されるため、
+    /// 文字列比較ではなくメジャークラスのビットから直接判定する
+    fn resolve_service_guid(class_of_device: u32) -> GUID {
+        let major = (class_of_device >> 8) & 0x1F;
+
+        match major {
+            0x04 => SERVICE_CLASS_HANDSFREE,
+            _ => SERVICE_CLASS_HID,
+        }
+    }
+
+    /// `BluetoothSetServiceState`を呼び出し、Win32エラーコードを`anyhow::Context`で包んで返す
+    fn set_service_state(address: &str, device_info: &BLUETOOTH_DEVICE_INFO, enable: bool) -> Result<()> {
+        let service_guid = Self::resolve_service_guid(device_info.ulClassofDevice);
+        let flags = if enable {
+            BLUETOOTH_SERVICE_ENABLE
+        } else {
+            BLUETOOTH_SERVICE_DISABLE
+        };
+
+        let result = unsafe {
+            BluetoothSetServiceState(HANDLE::default(), device_info, &service_guid, flags)
+        };
+
+        if result != WIN32_ERROR(0) {
+            return Err(anyhow::anyhow!("Win32エラーコード: {}", result.0))
+                .with_context(|| format!("デバイス {} のサービス状態変更に失敗しました", address));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for WindowsBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BluetoothBackend for WindowsBackend {
+    fn list_devices(&mut self) -> Result<Vec<BluetoothDevice>> {
+        println!("Bluetoothデバイスをスキャン中...");
+
+        let devices = self.enumerate_devices()?;
+
+        println!("{}個のBluetoothデバイスが見つかりました", devices.len());
+        Ok(devices)
+    }
+
+    fn connect_device(&mut self, address: &str) -> Result<()> {
+        println!("デバイス {} に接続を試行中...", address);
+
+        let device_info = self.find_device_info_by_address(address)?;
+        Self::set_service_state(address, &device_info, true)
+            .context("BluetoothSetServiceStateの呼び出しに失敗しました")?;
+
+        println!("デバイス {} に正常に接続しました", address);
+        Ok(())
+    }
+
+    fn disconnect_device(&mut self, address: &str) -> Result<()> {
+        println!("デバイス {} から切断中...", address);
+
+        let device_info = self.find_device_info_by_address(address)?;
+        Self::set_service_state(address, &device_info, false)
+            .context("BluetoothSetServiceStateの呼び出しに失敗しました")?;
+
+        println!("デバイス {} から正常に切断しました", address);
+        Ok(())
+    }
+
+    fn is_connected(&self, address: &str) -> Result<bool> {
+        // 呼び出しのたびに list_devices（Windows Bluetooth APIによる列挙）を行い、その結果から接続状態を確認する
+        let mut backend = WindowsBackend::new();
+        let devices = backend.list_devices()?;
+        Ok(devices.iter().any(|d| d.address == address && d.is_connected))
+    }
+
+    fn connection_info(&self, address: &str) -> Result<ConnectionInfo> {
+        // BLUETOOTH_DEVICE_INFOにはRSSI/送信電力が含まれないため、デバイスの存在だけ確認し、
+        // 値は取得不能（None）として返す
+        self.find_device_info_by_address(address)?;
+        Ok(ConnectionInfo::default())
+    }
+
+    fn pair(&mut self, address: &str) -> Result<()> {
+        println!("デバイス {} とのペアリングを試行中...", address);
+
+        if !self.is_valid_mac_address(address) {
+            return Err(anyhow::anyhow!("無効なMACアドレス形式です: {}", address));
+        }
+
+        let mut device_info = self.find_device_info_by_address(address)?;
+
+        let result = unsafe {
+            BluetoothAuthenticateDeviceEx(
+                None,
+                HANDLE::default(),
+                &mut device_info,
+                None,
+                MITMProtectionNotRequired,
+            )
+        };
+
+        if result != WIN32_ERROR(0) {
+            return Err(anyhow::anyhow!("Win32エラーコード: {}", result.0))
+                .with_context(|| format!("デバイス {} とのペアリングに失敗しました", address));
+        }
+
+        println!("デバイス {} とのペアリングが完了しました", address);
+        Ok(())
+    }
+
+    fn remove_bond(&mut self, address: &str) -> Result<()> {
+        println!("デバイス {} のボンディングを解除中...", address);
+
+        if !self.is_valid_mac_address(address) {
+            return Err(anyhow::anyhow!("無効なMACアドレス形式です: {}", address));
+        }
+
+        let target = Self::parse_bluetooth_address(address)?;
+
+        let result = unsafe { BluetoothRemoveDevice(&target) };
+
+        if result != WIN32_ERROR(0) {
+            return Err(anyhow::anyhow!("Win32エラーコード: {}", result.0))
+                .with_context(|| format!("デバイス {} のボンディング解除に失敗しました", address));
+        }
+
+        println!("デバイス {} のボンディングを解除しました", address);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_backend_valid_mac_address() {
+        let backend = WindowsBackend::new();
+
+        assert!(backend.is_valid_mac_address("AA:BB:CC:DD:EE:FF"));
+        assert!(!backend.is_valid_mac_address("AA:BB:CC:DD:EE"));
+        assert!(!backend.is_valid_mac_address("AA-BB-CC-DD-EE-FF"));
+        assert!(!backend.is_valid_mac_address(""));
+    }
+
+    #[test]
+    fn test_resolve_service_guid_headset_uses_handsfree() {
+        // 0x240404はclassify_device_typeでは"Headset"まで細分化されるが、
+        // サービス有効化はメジャークラス（Audio/Video）単位でHandsfreeを選ぶ
+        assert_eq!(classify_device_type(0x240404), "Headset");
+        assert_eq!(WindowsBackend::resolve_service_guid(0x240404), SERVICE_CLASS_HANDSFREE);
+    }
+
+    #[test]
+    fn test_resolve_service_guid_non_audio_uses_hid() {
+        assert_eq!(WindowsBackend::resolve_service_guid(0x000100), SERVICE_CLASS_HID);
+        assert_eq!(WindowsBackend::resolve_service_guid(0x002540), SERVICE_CLASS_HID);
+    }
+}
@@ -1,10 +1,19 @@
 //! CLI機能モジュール
 //! clapを使用してコマンドライン引数を処理し、各種コマンドを実行
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use crate::config::{AppConfig, get_config_path};
-use crate::bluetooth::BluetoothManager;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use crate::config::{AppConfig, ConnectionOutcome, get_config_path};
+use crate::bluetooth::{BluetoothManager, BluetoothScanFilter, BluetoothScanFilterSequence, BondState, is_blocklisted};
+
+/// 再接続ループの指数バックオフの上限
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(4);
+/// 再接続ループの初回待機時間
+const INITIAL_RECONNECT_BACKOFF: Duration = Duration::from_millis(500);
+/// --watch モードで接続状態を確認する間隔
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 /// Bluetoothデバイス接続管理ツール
 #[derive(Parser)]
@@ -14,6 +23,14 @@ use crate::bluetooth::BluetoothManager;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// モックフィクスチャファイルを指定してBluetoothバックエンドをモックに切り替える（テスト用の隠しフラグ）
+    #[arg(long, hide = true)]
+    pub mock: Option<PathBuf>,
+
+    /// コマンド省略時のデフォルト自動接続で、切断を検知するたびに再接続を繰り返す
+    #[arg(long)]
+    pub watch: bool,
 }
 
 /// 利用可能なコマンド一覧
@@ -24,6 +41,21 @@ pub enum Commands {
         /// 登録済みデバイスのみ表示
         #[arg(short, long)]
         registered: bool,
+        /// デバイス名の完全一致フィルタ
+        #[arg(long)]
+        name: Option<String>,
+        /// デバイス名の前方一致フィルタ
+        #[arg(long = "name-prefix")]
+        name_prefix: Option<String>,
+        /// サービスUUIDによるフィルタ（複数指定可）
+        #[arg(long = "service")]
+        service: Vec<String>,
+        /// デバイスタイプによるフィルタ
+        #[arg(long = "type")]
+        device_type: Option<String>,
+        /// 常に除外するUUID/アドレスを列挙したブロックリストファイル
+        #[arg(long)]
+        blocklist: Option<PathBuf>,
     },
     /// デバイスを登録
     Register {
@@ -41,11 +73,18 @@ pub enum Commands {
         address: String,
     },
     /// 現在の状態を表示
-    Status,
+    Status {
+        /// 機械可読なJSON形式で出力する
+        #[arg(long)]
+        json: bool,
+    },
     /// デバイスに接続
     Connect {
         /// デバイスのMACアドレス（省略時はデフォルトデバイス）
         address: Option<String>,
+        /// 接続が切れるたびに再発見・再接続を繰り返す
+        #[arg(long)]
+        watch: bool,
     },
     /// デバイスから切断
     Disconnect {
@@ -58,58 +97,193 @@ pub enum Commands {
         #[arg(short, long)]
         address: String,
     },
+    /// デバイスとペアリング（ボンディング）する
+    Pair {
+        /// デバイスのMACアドレス
+        #[arg(short, long)]
+        address: String,
+    },
+    /// デバイスのボンディングを解除する
+    Unpair {
+        /// デバイスのMACアドレス
+        #[arg(short, long)]
+        address: String,
+    },
 }
 
 /// CLIコマンドを実行
 pub fn run_command(cli: Cli) -> Result<()> {
+    let mut bluetooth_manager = match &cli.mock {
+        Some(fixture_path) => BluetoothManager::with_mock_fixture(fixture_path)?,
+        None => BluetoothManager::new(),
+    };
+    let watch = cli.watch;
+
     match cli.command {
-        Some(Commands::List { registered }) => {
-            handle_list_command(registered)
+        Some(Commands::List { registered, name, name_prefix, service, device_type, blocklist }) => {
+            handle_list_command(&mut bluetooth_manager, registered, name, name_prefix, service, device_type, blocklist)
         }
         Some(Commands::Register { address, name }) => {
-            handle_register_command(address, name)
+            handle_register_command(&mut bluetooth_manager, address, name)
         }
         Some(Commands::Unregister { address }) => {
             handle_unregister_command(address)
         }
-        Some(Commands::Status) => {
-            handle_status_command()
+        Some(Commands::Status { json }) => {
+            handle_status_command(&mut bluetooth_manager, json)
         }
-        Some(Commands::Connect { address }) => {
-            handle_connect_command(address)
+        Some(Commands::Connect { address, watch }) => {
+            handle_connect_command(&mut bluetooth_manager, address, watch)
         }
         Some(Commands::Disconnect { address }) => {
-            handle_disconnect_command(address)
+            handle_disconnect_command(&mut bluetooth_manager, address)
         }
         Some(Commands::SetDefault { address }) => {
             handle_set_default_command(address)
         }
+        Some(Commands::Pair { address }) => {
+            handle_pair_command(&mut bluetooth_manager, address)
+        }
+        Some(Commands::Unpair { address }) => {
+            handle_unpair_command(&mut bluetooth_manager, address)
+        }
         None => {
             // コマンドが指定されていない場合はデフォルト動作（自動接続）
-            handle_default_action()
+            handle_default_action(&mut bluetooth_manager, watch)
         }
     }
 }
 
+/// 指数バックオフで接続を試行した結果
+struct ReconnectOutcome {
+    /// 接続に成功したかどうか
+    success: bool,
+    /// 試行回数
+    attempts: u32,
+}
+
+/// デバイスへの接続を、タイムアウトまで指数バックオフで再試行する
+/// 毎回の試行の前に `list_devices` でデバイスが発見可能かを確認する
+fn connect_with_backoff(
+    bluetooth_manager: &mut BluetoothManager,
+    address: &str,
+    timeout_secs: u32,
+) -> ReconnectOutcome {
+    let deadline = Instant::now() + Duration::from_secs(timeout_secs as u64);
+    let mut backoff = INITIAL_RECONNECT_BACKOFF;
+    let mut attempt = 0u32;
+
+    loop {
+        attempt += 1;
+        println!("接続試行 {} 回目...", attempt);
+
+        let discovered_device = bluetooth_manager.list_devices()
+            .ok()
+            .and_then(|devices| devices.into_iter().find(|d| d.address == address));
+
+        match discovered_device {
+            None => {
+                println!("  デバイスが見つかりません: {}", address);
+            }
+            Some(device) => {
+                if device.bond_state == BondState::NotBonded {
+                    println!("  未ペアリングのデバイスです。ペアリングを試行します...");
+                    if let Err(e) = bluetooth_manager.pair(address) {
+                        println!("  ペアリングに失敗しました: {}", e);
+                    }
+                }
+
+                match bluetooth_manager.connect_device(address) {
+                    Ok(()) => {
+                        return ReconnectOutcome { success: true, attempts: attempt };
+                    }
+                    Err(e) => {
+                        println!("  接続に失敗しました: {}", e);
+                    }
+                }
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return ReconnectOutcome { success: false, attempts: attempt };
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        std::thread::sleep(backoff.min(remaining));
+        backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+    }
+}
+
+/// --watch モード: 接続を確立した後も切断を検知するたびに再接続を繰り返す
+fn watch_connection(bluetooth_manager: &mut BluetoothManager, address: &str, timeout_secs: u32) -> Result<()> {
+    loop {
+        let outcome = connect_with_backoff(bluetooth_manager, address, timeout_secs);
+        if !outcome.success {
+            return Err(anyhow::anyhow!(
+                "接続タイムアウトです（{}秒、{}回試行）",
+                timeout_secs,
+                outcome.attempts
+            ));
+        }
+        println!("接続が完了しました。（{} 回目の試行）", outcome.attempts);
+        println!("接続を監視しています。切断を検知したら自動的に再接続します。（Ctrl+Cで終了）");
+
+        loop {
+            std::thread::sleep(WATCH_POLL_INTERVAL);
+            if !bluetooth_manager.is_connected(address).unwrap_or(false) {
+                println!("接続が切断されました。再接続を試みます。");
+                break;
+            }
+        }
+    }
+}
+
+/// ブロックリストファイルを読み込む（1行1エントリ、`#`始まりの行は無視）
+fn load_blocklist(path: &std::path::Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("ブロックリストファイル {:?} の読み込みに失敗しました", path))?;
+
+    Ok(content.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect())
+}
+
 /// listコマンドの処理
-fn handle_list_command(registered_only: bool) -> Result<()> {
+fn handle_list_command(
+    bluetooth_manager: &mut BluetoothManager,
+    registered_only: bool,
+    name: Option<String>,
+    name_prefix: Option<String>,
+    service: Vec<String>,
+    device_type: Option<String>,
+    blocklist: Option<PathBuf>,
+) -> Result<()> {
     let config_path = get_config_path()?;
     let config = if config_path.exists() {
         AppConfig::load_from_file(&config_path)?
     } else {
         AppConfig::default()
     };
-    
+
     if registered_only {
         println!("=== 登録済みデバイス一覧 ===");
         if config.registered_devices.is_empty() {
             println!("登録済みデバイスはありません。");
         } else {
+            // アドレスをキーにしたボンディング状態のルックアップ（取得できない場合は空のまま）
+            let bond_states: std::collections::HashMap<String, BondState> = bluetooth_manager.list_devices()
+                .map(|devices| devices.into_iter().map(|d| (d.address, d.bond_state)).collect())
+                .unwrap_or_default();
+
             for (index, device) in config.registered_devices.iter().enumerate() {
                 let is_default = config.default_device.as_ref() == Some(&device.address);
                 let default_mark = if is_default { " [デフォルト]" } else { "" };
-                
-                println!("{}. {}{}", index + 1, device.name, default_mark);
+                let is_bonded = bond_states.get(&device.address) == Some(&BondState::Bonded);
+                let bond_mark = if is_bonded { " [ペアリング済み]" } else { "" };
+
+                println!("{}. {}{}{}", index + 1, device.name, default_mark, bond_mark);
                 println!("   アドレス: {}", device.address);
                 println!("   タイプ: {}", device.device_type);
                 if let Some(last_connected) = &device.last_connected {
@@ -120,11 +294,31 @@ fn handle_list_command(registered_only: bool) -> Result<()> {
         }
     } else {
         println!("=== 利用可能なBluetoothデバイス一覧 ===");
-        
-        // Bluetoothマネージャーを使用してデバイス一覧を取得
-        let bluetooth_manager = BluetoothManager::new();
-        match bluetooth_manager.list_devices() {
+
+        let blocklist_entries = match &blocklist {
+            Some(path) => load_blocklist(path)?,
+            None => Vec::new(),
+        };
+
+        let has_filter = name.is_some() || name_prefix.is_some() || !service.is_empty() || device_type.is_some();
+        let filters = if has_filter {
+            BluetoothScanFilterSequence(vec![BluetoothScanFilter {
+                name,
+                name_prefix,
+                services: service,
+                device_type,
+            }])
+        } else {
+            BluetoothScanFilterSequence::default()
+        };
+
+        // Bluetoothマネージャーを使用してスキャンフィルタ適用済みのデバイス一覧を取得
+        match bluetooth_manager.list_devices_filtered(&filters) {
             Ok(bluetooth_devices) => {
+                let bluetooth_devices: Vec<_> = bluetooth_devices.into_iter()
+                    .filter(|d| !is_blocklisted(d, &blocklist_entries))
+                    .collect();
+
                 if bluetooth_devices.is_empty() {
                     println!("利用可能なBluetoothデバイスが見つかりませんでした。");
                 } else {
@@ -134,12 +328,14 @@ fn handle_list_command(registered_only: bool) -> Result<()> {
                         let is_registered = config.registered_devices.iter()
                             .any(|reg_dev| reg_dev.address == device.address);
                         let registered_mark = if is_registered { " [登録済み]" } else { "" };
-                        
-                        println!("  - {} ({}){}{}", 
-                            device.name, 
-                            device.address, 
+                        let bond_mark = if device.bond_state == BondState::Bonded { " [ペアリング済み]" } else { "" };
+
+                        println!("  - {} ({}){}{}{}",
+                            device.name,
+                            device.address,
                             connection_status,
-                            registered_mark
+                            registered_mark,
+                            bond_mark
                         );
                         println!("    タイプ: {}", device.device_type);
                     }
@@ -165,20 +361,27 @@ fn handle_list_command(registered_only: bool) -> Result<()> {
 }
 
 /// registerコマンドの処理
-fn handle_register_command(address: String, name: Option<String>) -> Result<()> {
+fn handle_register_command(bluetooth_manager: &mut BluetoothManager, address: String, name: Option<String>) -> Result<()> {
     let config_path = get_config_path()?;
     let mut config = if config_path.exists() {
         AppConfig::load_from_file(&config_path)?
     } else {
         AppConfig::default()
     };
-    
+
     let device_name = name.unwrap_or_else(|| format!("Device-{}", &address[..8]));
-    
-    // デバイスを登録
-    config.register_device(device_name.clone(), address.clone(), "Unknown".to_string());
+
+    // デバイスタイプはClass of Deviceから自動分類するため、一覧から対象デバイスのCoDを探す
+    // 見つからない場合は0（"Unknown"に分類される）を渡す
+    let class_of_device = bluetooth_manager.list_devices()
+        .ok()
+        .and_then(|devices| devices.into_iter().find(|d| d.address == address))
+        .map(|d| d.class_of_device)
+        .unwrap_or(0);
+
+    config.register_device(device_name.clone(), address.clone(), class_of_device);
     config.save_to_file(&config_path)?;
-    
+
     println!("デバイスを登録しました: {} ({})", device_name, address);
     Ok(())
 }
@@ -204,32 +407,60 @@ fn handle_unregister_command(address: String) -> Result<()> {
 }
 
 /// statusコマンドの処理
-fn handle_status_command() -> Result<()> {
+fn handle_status_command(bluetooth_manager: &mut BluetoothManager, json: bool) -> Result<()> {
     let config_path = get_config_path()?;
     let config = if config_path.exists() {
         AppConfig::load_from_file(&config_path)?
     } else {
-        println!("設定ファイルが存在しません。初期化してください。");
+        if json {
+            println!("{{}}");
+        } else {
+            println!("設定ファイルが存在しません。初期化してください。");
+        }
         return Ok(());
     };
-    
+
+    let stats = config.connection_stats();
+
+    // アドレスをキーにしたボンディング状態のルックアップ（取得できない場合は空のまま）
+    let bond_states: std::collections::HashMap<String, BondState> = bluetooth_manager.list_devices()
+        .map(|devices| devices.into_iter().map(|d| (d.address, d.bond_state)).collect())
+        .unwrap_or_default();
+
+    if json {
+        let output = serde_json::json!({
+            "config_path": config_path,
+            "auto_connect": config.auto_connect,
+            "connection_timeout": config.connection_timeout,
+            "default_device": config.default_device,
+            "registered_devices": config.registered_devices,
+            "connection_stats": stats,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)
+            .context("状態のJSONシリアライズに失敗しました")?);
+        return Ok(());
+    }
+
     println!("=== Bluetooth デバイス管理ツール 状態 ===");
     println!("設定ファイル: {:?}", config_path);
     println!("自動接続: {}", if config.auto_connect { "有効" } else { "無効" });
     println!("接続タイムアウト: {}秒", config.connection_timeout);
-    
+
     if let Some(default_device) = &config.default_device {
         println!("デフォルトデバイス: {}", default_device);
     } else {
         println!("デフォルトデバイス: 未設定");
     }
-    
+
     println!("\n=== 登録済みデバイス ({}) ===", config.registered_devices.len());
     if config.registered_devices.is_empty() {
         println!("登録済みデバイスはありません。");
     } else {
         for device in &config.registered_devices {
-            println!("  名前: {}", device.name);
+            let is_bonded = bond_states.get(&device.address) == Some(&BondState::Bonded);
+            let bond_mark = if is_bonded { " [ペアリング済み]" } else { "" };
+
+            println!("  名前: {}{}", device.name, bond_mark);
             println!("  アドレス: {}", device.address);
             println!("  タイプ: {}", device.device_type);
             if let Some(last_connected) = &device.last_connected {
@@ -237,22 +468,34 @@ fn handle_status_command() -> Result<()> {
             } else {
                 println!("  最終接続: なし");
             }
+
+            if let Some(device_stats) = stats.iter().find(|s| s.address == device.address) {
+                println!("  接続成功回数: {}", device_stats.total_successful_connects);
+                match device_stats.average_connect_latency_ms {
+                    Some(avg) => println!("  平均接続時間: {:.0}ms", avg),
+                    None => println!("  平均接続時間: -"),
+                }
+                match &device_stats.last_failure {
+                    Some(last_failure) => println!("  直近の失敗: {}", last_failure),
+                    None => println!("  直近の失敗: なし"),
+                }
+            }
             println!();
         }
     }
-    
+
     Ok(())
 }
 
 /// connectコマンドの処理
-fn handle_connect_command(address: Option<String>) -> Result<()> {
+fn handle_connect_command(bluetooth_manager: &mut BluetoothManager, address: Option<String>, watch: bool) -> Result<()> {
     let config_path = get_config_path()?;
     let config = if config_path.exists() {
         AppConfig::load_from_file(&config_path)?
     } else {
         AppConfig::default()
     };
-    
+
     let target_address = match address {
         Some(addr) => addr,
         None => {
@@ -269,28 +512,56 @@ fn handle_connect_command(address: Option<String>) -> Result<()> {
             }
         }
     };
-    
-    // Bluetoothマネージャーを使用して接続
-    let bluetooth_manager = BluetoothManager::new();
-    match bluetooth_manager.connect_device(&target_address) {
-        Ok(()) => {
-            println!("接続が完了しました。");
-            
-            // 接続成功時に最終接続時刻を更新（設定ファイルに保存）
-            // TODO: 実際の実装では現在時刻を記録
-        }
-        Err(e) => {
-            println!("接続に失敗しました: {}", e);
-        }
+
+    if watch {
+        return watch_connection(bluetooth_manager, &target_address, config.connection_timeout);
     }
-    
+
+    // タイムアウトまで指数バックオフで接続を試行
+    let started_at = Instant::now();
+    let outcome = connect_with_backoff(bluetooth_manager, &target_address, config.connection_timeout);
+    let elapsed_ms = started_at.elapsed().as_millis() as u64;
+
+    if let Err(e) = record_connection_attempt(&config_path, &target_address, &outcome, elapsed_ms) {
+        println!("接続履歴の記録に失敗しました: {}", e);
+    }
+
+    if outcome.success {
+        println!("接続が完了しました。（{} 回目の試行）", outcome.attempts);
+    } else {
+        println!("接続に失敗しました（{}秒、{}回試行）。", config.connection_timeout, outcome.attempts);
+    }
+
+    Ok(())
+}
+
+/// 接続試行の結果を設定ファイルの接続履歴に記録する
+fn record_connection_attempt(
+    config_path: &std::path::Path,
+    address: &str,
+    outcome: &ReconnectOutcome,
+    duration_ms: u64,
+) -> Result<()> {
+    let mut config = if config_path.exists() {
+        AppConfig::load_from_file(config_path)?
+    } else {
+        AppConfig::default()
+    };
+
+    let connection_outcome = if outcome.success {
+        ConnectionOutcome::Success
+    } else {
+        ConnectionOutcome::Failure
+    };
+
+    config.record_connection_event(address, connection_outcome, duration_ms, outcome.attempts);
+    config.save_to_file(config_path)?;
+
     Ok(())
 }
 
 /// disconnectコマンドの処理
-fn handle_disconnect_command(address: Option<String>) -> Result<()> {
-    let bluetooth_manager = BluetoothManager::new();
-    
+fn handle_disconnect_command(bluetooth_manager: &mut BluetoothManager, address: Option<String>) -> Result<()> {
     match address {
         Some(addr) => {
             // 指定されたデバイスから切断
@@ -363,10 +634,38 @@ fn handle_set_default_command(address: String) -> Result<()> {
     Ok(())
 }
 
+/// pairコマンドの処理
+fn handle_pair_command(bluetooth_manager: &mut BluetoothManager, address: String) -> Result<()> {
+    match bluetooth_manager.pair(&address) {
+        Ok(()) => {
+            println!("デバイス {} とのペアリングが完了しました。", address);
+        }
+        Err(e) => {
+            println!("ペアリングに失敗しました: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// unpairコマンドの処理
+fn handle_unpair_command(bluetooth_manager: &mut BluetoothManager, address: String) -> Result<()> {
+    match bluetooth_manager.remove_bond(&address) {
+        Ok(()) => {
+            println!("デバイス {} のボンディングを解除しました。", address);
+        }
+        Err(e) => {
+            println!("ボンディングの解除に失敗しました: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
 /// デフォルト動作（コマンド未指定時）
-fn handle_default_action() -> Result<()> {
+fn handle_default_action(bluetooth_manager: &mut BluetoothManager, watch: bool) -> Result<()> {
     println!("=== Bluetoothデバイス自動接続 ===");
-    
+
     let config_path = get_config_path()?;
     let config = if config_path.exists() {
         AppConfig::load_from_file(&config_path)?
@@ -374,7 +673,7 @@ fn handle_default_action() -> Result<()> {
         println!("設定ファイルが存在しません。初期化してください。");
         return Ok(());
     };
-    
+
     // 自動接続が無効の場合
     if !config.auto_connect {
         println!("自動接続が無効になっています。");
@@ -385,20 +684,28 @@ fn handle_default_action() -> Result<()> {
         println!("  status                  - 現在の状態を表示");
         return Ok(());
     }
-    
+
     // デフォルトデバイスが設定されている場合
     if let Some(default_address) = &config.default_device {
         println!("デフォルトデバイスに自動接続します: {}", default_address);
-        
-        let bluetooth_manager = BluetoothManager::new();
-        match bluetooth_manager.connect_device(default_address) {
-            Ok(()) => {
-                println!("自動接続が完了しました。");
-            }
-            Err(e) => {
-                println!("自動接続に失敗しました: {}", e);
-                println!("手動で接続を試行してください。");
-            }
+
+        if watch {
+            return watch_connection(bluetooth_manager, default_address, config.connection_timeout);
+        }
+
+        let started_at = Instant::now();
+        let outcome = connect_with_backoff(bluetooth_manager, default_address, config.connection_timeout);
+        let elapsed_ms = started_at.elapsed().as_millis() as u64;
+
+        if let Err(e) = record_connection_attempt(&config_path, default_address, &outcome, elapsed_ms) {
+            println!("接続履歴の記録に失敗しました: {}", e);
+        }
+
+        if outcome.success {
+            println!("自動接続が完了しました。（{} 回目の試行）", outcome.attempts);
+        } else {
+            println!("自動接続に失敗しました（{}秒、{}回試行）。", config.connection_timeout, outcome.attempts);
+            println!("手動で接続を試行してください。");
         }
     } else {
         println!("デフォルトデバイスが設定されていません。");
@@ -417,4 +724,101 @@ fn handle_default_action() -> Result<()> {
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bluetooth::{BluetoothManager, BondState, MockBackend};
+
+    fn device(address: &str, bond_state: BondState) -> crate::bluetooth::BluetoothDevice {
+        crate::bluetooth::BluetoothDevice {
+            name: "Test Device".to_string(),
+            address: address.to_string(),
+            is_connected: false,
+            device_type: "Unknown".to_string(),
+            service_uuids: Vec::new(),
+            class_of_device: 0,
+            bond_state,
+        }
+    }
+
+    fn write_temp_file(contents: &str) -> std::path::PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("test_blocklist_{}_{}.txt", std::process::id(), contents.len()));
+        std::fs::write(&path, contents).expect("テストファイルの書き込みに失敗しました");
+        path
+    }
+
+    fn cleanup(path: &std::path::Path) {
+        if path.exists() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_connect_with_backoff_succeeds_on_first_attempt() {
+        let mut manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(vec![
+            device("AA:BB:CC:DD:EE:FF", BondState::Bonded),
+        ])));
+
+        let outcome = connect_with_backoff(&mut manager, "AA:BB:CC:DD:EE:FF", 5);
+
+        assert!(outcome.success);
+        assert_eq!(outcome.attempts, 1);
+    }
+
+    #[test]
+    fn test_connect_with_backoff_pairs_unbonded_device_before_connecting() {
+        let mut manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(vec![
+            device("AA:BB:CC:DD:EE:FF", BondState::NotBonded),
+        ])));
+
+        let outcome = connect_with_backoff(&mut manager, "AA:BB:CC:DD:EE:FF", 5);
+
+        assert!(outcome.success);
+        assert_eq!(outcome.attempts, 1);
+        let devices = manager.list_devices().expect("list_devicesに失敗しました");
+        assert_eq!(devices[0].bond_state, BondState::Bonded);
+    }
+
+    #[test]
+    fn test_connect_with_backoff_gives_up_when_device_not_found() {
+        let mut manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(Vec::new())));
+
+        // timeout_secsを0にすることで、初回試行後すぐにdeadlineに達し失敗が返る
+        let outcome = connect_with_backoff(&mut manager, "AA:BB:CC:DD:EE:FF", 0);
+
+        assert!(!outcome.success);
+        assert_eq!(outcome.attempts, 1);
+    }
+
+    #[test]
+    fn test_connect_with_backoff_retries_until_deadline() {
+        let mut manager = BluetoothManager::with_backend(Box::new(MockBackend::with_devices(Vec::new())));
+
+        // 初回待機はINITIAL_RECONNECT_BACKOFF(500ms)のため、1秒のタイムアウトでは複数回試行される
+        let outcome = connect_with_backoff(&mut manager, "AA:BB:CC:DD:EE:FF", 1);
+
+        assert!(!outcome.success);
+        assert!(outcome.attempts >= 2);
+    }
+
+    #[test]
+    fn test_load_blocklist_skips_comments_and_blank_lines() {
+        let path = write_temp_file("# comment\n\nAA:BB:CC:DD:EE:FF\n  \n00:11:22:33:44:55\n");
+
+        let entries = load_blocklist(&path).expect("load_blocklistに失敗しました");
+        cleanup(&path);
+
+        assert_eq!(entries, vec!["AA:BB:CC:DD:EE:FF".to_string(), "00:11:22:33:44:55".to_string()]);
+    }
+
+    #[test]
+    fn test_load_blocklist_missing_file_is_error() {
+        let path = std::env::temp_dir().join("does_not_exist_blocklist.txt");
+        cleanup(&path);
+
+        assert!(load_blocklist(&path).is_err());
+    }
+}
@@ -2,10 +2,13 @@
 //! アプリケーションの設定ファイル（config.toml）の読み書きを管理
 
 use anyhow::{Context, Result};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use crate::bluetooth::classify_device_type;
+
 /// アプリケーション設定を表す構造体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -17,6 +20,9 @@ pub struct AppConfig {
     pub auto_connect: bool,
     /// 接続タイムアウト（秒）
     pub connection_timeout: u32,
+    /// 接続試行の履歴（Android側のbond/ACL状態変化に伴うメトリクス記録を参考にしたもの）
+    #[serde(default)]
+    pub connection_events: Vec<ConnectionEvent>,
 }
 
 /// 登録済みデバイス情報
@@ -32,6 +38,44 @@ pub struct RegisteredDevice {
     pub last_connected: Option<String>,
 }
 
+/// 接続試行の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionOutcome {
+    /// 接続に成功した
+    Success,
+    /// 接続に失敗した（タイムアウトを含む）
+    Failure,
+}
+
+/// 1回の接続試行の記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionEvent {
+    /// 対象デバイスのMACアドレス
+    pub address: String,
+    /// 試行時刻（RFC 3339）
+    pub timestamp: String,
+    /// 試行結果
+    pub outcome: ConnectionOutcome,
+    /// 接続確立（または諦め）までの所要時間（ミリ秒）
+    pub duration_ms: u64,
+    /// 指数バックオフでの再試行込みの試行回数
+    pub attempt_count: u32,
+}
+
+/// 登録済みデバイス1件分の接続統計
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceConnectionStats {
+    /// 対象デバイスのMACアドレス
+    pub address: String,
+    /// 成功した接続の累計回数
+    pub total_successful_connects: usize,
+    /// 直近の失敗時刻（RFC 3339、失敗履歴がない場合はNone）
+    pub last_failure: Option<String>,
+    /// 成功した接続の平均所要時間（ミリ秒、成功履歴がない場合はNone）
+    pub average_connect_latency_ms: Option<f64>,
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -39,6 +83,7 @@ impl Default for AppConfig {
             default_device: None,
             auto_connect: false,
             connection_timeout: 30,
+            connection_events: Vec::new(),
         }
     }
 }
@@ -67,7 +112,10 @@ impl AppConfig {
     }
 
     /// デバイスを登録
-    pub fn register_device(&mut self, name: String, address: String, device_type: String) {
+    /// `device_type` はClass of Deviceから自動分類する（0または未知の場合は"Unknown"）
+    pub fn register_device(&mut self, name: String, address: String, class_of_device: u32) {
+        let device_type = classify_device_type(class_of_device);
+
         // 既存のデバイスを更新するか、新しいデバイスを追加
         if let Some(device) = self.registered_devices.iter_mut().find(|d| d.address == address) {
             device.name = name;
@@ -98,6 +146,64 @@ impl AppConfig {
     pub fn set_default_device(&mut self, address: Option<String>) {
         self.default_device = address;
     }
+
+    /// 接続試行の結果を記録する
+    /// 成功時は該当デバイスが登録済みであれば`last_connected`も現在時刻で更新する
+    pub fn record_connection_event(
+        &mut self,
+        address: &str,
+        outcome: ConnectionOutcome,
+        duration_ms: u64,
+        attempt_count: u32,
+    ) {
+        let timestamp = Utc::now().to_rfc3339();
+
+        if outcome == ConnectionOutcome::Success {
+            if let Some(device) = self.registered_devices.iter_mut().find(|d| d.address == address) {
+                device.last_connected = Some(timestamp.clone());
+            }
+        }
+
+        self.connection_events.push(ConnectionEvent {
+            address: address.to_string(),
+            timestamp,
+            outcome,
+            duration_ms,
+            attempt_count,
+        });
+    }
+
+    /// 登録済みデバイスごとの接続統計を集計する
+    pub fn connection_stats(&self) -> Vec<DeviceConnectionStats> {
+        self.registered_devices.iter().map(|device| {
+            let events: Vec<_> = self.connection_events.iter()
+                .filter(|e| e.address == device.address)
+                .collect();
+
+            let successes: Vec<_> = events.iter()
+                .filter(|e| e.outcome == ConnectionOutcome::Success)
+                .collect();
+
+            let average_connect_latency_ms = if successes.is_empty() {
+                None
+            } else {
+                let total: u64 = successes.iter().map(|e| e.duration_ms).sum();
+                Some(total as f64 / successes.len() as f64)
+            };
+
+            let last_failure = events.iter()
+                .filter(|e| e.outcome == ConnectionOutcome::Failure)
+                .next_back()
+                .map(|e| e.timestamp.clone());
+
+            DeviceConnectionStats {
+                address: device.address.clone(),
+                total_successful_connects: successes.len(),
+                last_failure,
+                average_connect_latency_ms,
+            }
+        }).collect()
+    }
 }
 
 /// 設定ファイルのパスを取得
@@ -158,56 +264,69 @@ mod tests {
         config.register_device(
             "Test Device".to_string(),
             "AA:BB:CC:DD:EE:FF".to_string(),
-            "Test Type".to_string()
+            0x000100
         );
-        
+
         assert_eq!(config.registered_devices.len(), 1);
         let device = &config.registered_devices[0];
         assert_eq!(device.name, "Test Device");
         assert_eq!(device.address, "AA:BB:CC:DD:EE:FF");
-        assert_eq!(device.device_type, "Test Type");
+        assert_eq!(device.device_type, "Computer");
         assert!(device.last_connected.is_none());
     }
 
     #[test]
     fn test_register_device_update_existing() {
         let mut config = AppConfig::default();
-        
+
         // 最初のデバイス登録
         config.register_device(
             "Old Name".to_string(),
             "AA:BB:CC:DD:EE:FF".to_string(),
-            "Old Type".to_string()
+            0x000100
         );
-        
+
         // 同じアドレスで再登録（更新）
         config.register_device(
             "New Name".to_string(),
             "AA:BB:CC:DD:EE:FF".to_string(),
-            "New Type".to_string()
+            0x000200
         );
-        
+
         // デバイス数は1つのまま
         assert_eq!(config.registered_devices.len(), 1);
         let device = &config.registered_devices[0];
         assert_eq!(device.name, "New Name");
-        assert_eq!(device.device_type, "New Type");
+        assert_eq!(device.device_type, "Phone");
+    }
+
+    #[test]
+    fn test_register_device_unknown_cod_falls_back_to_unknown() {
+        let mut config = AppConfig::default();
+
+        config.register_device(
+            "Unidentified Device".to_string(),
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            0
+        );
+
+        assert_eq!(config.registered_devices[0].device_type, "Unknown");
     }
 
     #[test]
     fn test_unregister_device() {
         let mut config = AppConfig::default();
-        
+
         config.register_device(
             "Test Device".to_string(),
             "AA:BB:CC:DD:EE:FF".to_string(),
-            "Test Type".to_string()
+            0x000100
         );
-        
+
         // 存在するデバイスの削除
         assert!(config.unregister_device("AA:BB:CC:DD:EE:FF"));
         assert!(config.registered_devices.is_empty());
-        
+
         // 存在しないデバイスの削除
         assert!(!config.unregister_device("11:22:33:44:55:66"));
     }
@@ -215,13 +334,13 @@ mod tests {
     #[test]
     fn test_get_registered_device() {
         let mut config = AppConfig::default();
-        
+
         config.register_device(
             "Test Device".to_string(),
             "AA:BB:CC:DD:EE:FF".to_string(),
-            "Test Type".to_string()
+            0x000100
         );
-        
+
         // 存在するデバイスの取得
         let device = config.get_registered_device("AA:BB:CC:DD:EE:FF");
         assert!(device.is_some());
@@ -254,7 +373,7 @@ mod tests {
         config.register_device(
             "Test Device".to_string(),
             "AA:BB:CC:DD:EE:FF".to_string(),
-            "Test Type".to_string()
+            0x000100
         );
         config.set_default_device(Some("AA:BB:CC:DD:EE:FF".to_string()));
         config.auto_connect = true;
@@ -282,4 +401,69 @@ mod tests {
         let result = AppConfig::load_from_file(&nonexistent_path);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_record_connection_event_success_updates_last_connected() {
+        let mut config = AppConfig::default();
+        config.register_device(
+            "Test Device".to_string(),
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            0x000100
+        );
+
+        config.record_connection_event("AA:BB:CC:DD:EE:FF", ConnectionOutcome::Success, 250, 1);
+
+        assert_eq!(config.connection_events.len(), 1);
+        assert!(config.registered_devices[0].last_connected.is_some());
+    }
+
+    #[test]
+    fn test_record_connection_event_failure_does_not_touch_last_connected() {
+        let mut config = AppConfig::default();
+        config.register_device(
+            "Test Device".to_string(),
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            0x000100
+        );
+
+        config.record_connection_event("AA:BB:CC:DD:EE:FF", ConnectionOutcome::Failure, 30_000, 5);
+
+        assert_eq!(config.connection_events.len(), 1);
+        assert!(config.registered_devices[0].last_connected.is_none());
+    }
+
+    #[test]
+    fn test_connection_stats_aggregates_per_device() {
+        let mut config = AppConfig::default();
+        config.register_device(
+            "Test Device".to_string(),
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            0x000100
+        );
+
+        config.record_connection_event("AA:BB:CC:DD:EE:FF", ConnectionOutcome::Failure, 30_000, 3);
+        config.record_connection_event("AA:BB:CC:DD:EE:FF", ConnectionOutcome::Success, 200, 1);
+        config.record_connection_event("AA:BB:CC:DD:EE:FF", ConnectionOutcome::Success, 400, 1);
+
+        let stats = config.connection_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].total_successful_connects, 2);
+        assert_eq!(stats[0].average_connect_latency_ms, Some(300.0));
+        assert!(stats[0].last_failure.is_some());
+    }
+
+    #[test]
+    fn test_connection_stats_with_no_events_is_empty_averages() {
+        let mut config = AppConfig::default();
+        config.register_device(
+            "Test Device".to_string(),
+            "AA:BB:CC:DD:EE:FF".to_string(),
+            0x000100
+        );
+
+        let stats = config.connection_stats();
+        assert_eq!(stats[0].total_successful_connects, 0);
+        assert_eq!(stats[0].average_connect_latency_ms, None);
+        assert_eq!(stats[0].last_failure, None);
+    }
 }
\ No newline at end of file